@@ -1,5 +1,7 @@
-use apollo_client::conf::{meta::IpValue, requests::CachedFetchRequest, ApolloConfClientBuilder};
-use ini::Properties;
+use apollo_client::{
+    conf::{meta::IpValue, requests::CachedFetchRequest, ApolloConfClientBuilder},
+    properties::Properties,
+};
 use std::error::Error;
 use url::Url;
 
@@ -0,0 +1,34 @@
+use apollo_client::conf::{
+    blocking::BlockingApolloConfClient, meta::IpValue, requests::WatchRequest,
+    ApolloConfClientBuilder,
+};
+use std::error::Error;
+use url::Url;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    // Create blocking configuration client.
+    let client = BlockingApolloConfClient::new(
+        ApolloConfClientBuilder::new_via_config_service(Url::parse("http://localhost:8080")?)?
+            .build()?,
+    )?;
+
+    // Request apollo notification api, and fetch configuration when notified.
+    let watch = client.watch(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        namespace_names: vec!["application.properties".into(), "application.json".into()],
+        ip: Some(IpValue::HostName),
+        ..Default::default()
+    });
+
+    // This is a dead loop, `next()` is returned when configuration is changed.
+    for response in watch {
+        let responses = response?;
+        for response in responses {
+            let _ = dbg!(response);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! Blocking configuration apis client, for the programs not running in async context.
+//!
+//! The requests are executed by the [ApolloConfClient] on an internal current-thread tokio
+//! runtime, so the request types, signing and url building are exactly the same.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use apollo_client::conf::{
+//!     blocking::BlockingApolloConfClient, meta::IpValue, requests::FetchRequest,
+//!     ApolloConfClientBuilder,
+//! };
+//! use std::error::Error;
+//! use url::Url;
+//!
+//! fn main() -> Result<(), Box<dyn Error>> {
+//!     let client = BlockingApolloConfClient::new(
+//!         ApolloConfClientBuilder::new_via_config_service(Url::parse("http://localhost:8080")?)?
+//!             .build()?,
+//!     )?;
+//!
+//!     let response = client.fetch(FetchRequest {
+//!         app_id: "SampleApp".to_string(),
+//!         namespace_name: "application.json".to_string(),
+//!         ip: Some(IpValue::HostName),
+//!         ..Default::default()
+//!     })?;
+//!     dbg!(response);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    conf::{
+        meta::Notification,
+        requests::{CachedFetchRequest, FetchRequest, NotifyRequest, WatchRequest},
+        responses::FetchResponse,
+        watch::WatchHandle,
+        ApolloConfClient,
+    },
+    errors::ApolloClientResult,
+    properties::Properties,
+};
+use futures_core::Stream;
+use futures_util::StreamExt;
+use std::{collections::HashMap, fmt, pin::Pin, sync::Arc};
+use tokio::runtime::{Builder, Runtime};
+
+/// Blocking version of [ApolloConfClient].
+///
+/// # Panic
+///
+/// The methods panic if called in the context of async runtime.
+#[derive(Clone)]
+pub struct BlockingApolloConfClient {
+    client: ApolloConfClient,
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingApolloConfClient {
+    /// Create the blocking client, with an internal current-thread tokio runtime.
+    pub fn new(client: ApolloConfClient) -> ApolloClientResult<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Blocking version of [ApolloConfClient::cached_fetch].
+    pub fn cached_fetch(&self, request: CachedFetchRequest) -> ApolloClientResult<Properties> {
+        self.runtime.block_on(self.client.cached_fetch(request))
+    }
+
+    /// Blocking version of [ApolloConfClient::fetch].
+    pub fn fetch(&self, request: FetchRequest) -> ApolloClientResult<FetchResponse> {
+        self.runtime.block_on(self.client.fetch(request))
+    }
+
+    /// Blocking version of [ApolloConfClient::notify].
+    pub fn notify(&self, request: NotifyRequest) -> ApolloClientResult<Vec<Notification>> {
+        self.runtime.block_on(self.client.notify(request))
+    }
+
+    /// Blocking version of [ApolloConfClient::watch_with_handle], the returned iterator blocks
+    /// until the configuration changed, and ends after [WatchHandle::stop] called, which can be
+    /// called in other threads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_client::conf::{blocking::BlockingApolloConfClient, requests::WatchRequest};
+    ///
+    /// let client: BlockingApolloConfClient = todo!();
+    ///
+    /// let watch = client.watch(WatchRequest {
+    ///     app_id: "SampleApp".to_string(),
+    ///     namespace_names: vec!["application.properties".into()],
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let handle = watch.handle().clone();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_secs(60));
+    ///     handle.stop();
+    /// });
+    ///
+    /// for responses in watch {
+    ///     let _ = dbg!(responses);
+    /// }
+    /// ```
+    pub fn watch(&self, request: WatchRequest) -> BlockingWatch {
+        let (handle, stream) = self.client.watch_with_handle(request);
+        BlockingWatch {
+            handle,
+            stream: Box::pin(stream),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+type WatchStream = Pin<
+    Box<
+        dyn Stream<Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>>
+            + Send,
+    >,
+>;
+
+/// Iterator of the watching, created by [BlockingApolloConfClient::watch].
+pub struct BlockingWatch {
+    handle: WatchHandle,
+    stream: WatchStream,
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingWatch {
+    /// Handle to stop the watching, or subscribe and unsubscribe namespaces.
+    pub fn handle(&self) -> &WatchHandle {
+        &self.handle
+    }
+}
+
+impl Iterator for BlockingWatch {
+    type Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl fmt::Debug for BlockingWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingWatch")
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
@@ -0,0 +1,109 @@
+//! Local disk cache of fetched namespaces, used when apollo is unreachable.
+
+use crate::{
+    conf::{requests::FetchRequest, responses::FetchResponse},
+    errors::ApolloClientResult,
+    utils::canonicalize_namespace,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Local cache directory, the cache file of a namespace is named as
+/// `{app_id}+{cluster_name}+{namespace_name}.json`, similar to the java client. The namespace
+/// name is canonicalized, so `application` and `application.properties` share the cache file.
+#[derive(Clone, Debug)]
+pub(crate) struct LocalCache {
+    dir: PathBuf,
+}
+
+impl LocalCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, request: &FetchRequest) -> PathBuf {
+        self.dir.join(format!(
+            "{}+{}+{}.json",
+            request.app_id,
+            request.cluster_name,
+            canonicalize_namespace(&request.namespace_name)
+        ))
+    }
+
+    /// Persist the fetched response, write to a temporary file then rename, to avoid leaving a
+    /// broken cache file.
+    pub(crate) fn save(
+        &self,
+        request: &FetchRequest,
+        response: &FetchResponse,
+    ) -> ApolloClientResult<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(request);
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec(response)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Load the cached response, `None` if not cached.
+    pub(crate) fn load(&self, request: &FetchRequest) -> ApolloClientResult<Option<FetchResponse>> {
+        let path = self.path(request);
+        if !Path::exists(&path) {
+            return Ok(None);
+        }
+        let mut response: FetchResponse = serde_json::from_slice(&fs::read(path)?)?;
+        response.from_cache = true;
+        Ok(Some(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("apollo-client-cache-{}", std::process::id()));
+        let cache = LocalCache::new(dir.clone());
+        let request = FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application.properties".to_string(),
+            ..Default::default()
+        };
+
+        assert!(cache.load(&request).unwrap().is_none());
+
+        let response = FetchResponse {
+            app_id: "SampleApp".to_string(),
+            cluster: "default".to_string(),
+            namespace_name: "application.properties".to_string(),
+            configurations: HashMap::from([("timeout".to_string(), "100".to_string())]),
+            release_key: "20210101000000-1".to_string(),
+            from_cache: false,
+        };
+        cache.save(&request, &response).unwrap();
+        assert!(dir
+            .join("SampleApp+default+application.properties.json")
+            .exists());
+
+        let cached = cache.load(&request).unwrap().unwrap();
+        assert_eq!(cached.app_id, "SampleApp");
+        assert_eq!(cached.configurations["timeout"], "100");
+        assert_eq!(cached.release_key, "20210101000000-1");
+        assert!(cached.from_cache);
+
+        let request = FetchRequest {
+            namespace_name: "application".to_string(),
+            ..request
+        };
+        assert_eq!(
+            cache.load(&request).unwrap().unwrap().release_key,
+            "20210101000000-1"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
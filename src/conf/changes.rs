@@ -0,0 +1,163 @@
+//! Configuration change events, computed by diffing the configurations of a namespace.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Change type of a configuration key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeType {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// Change of a configuration key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub change_type: ChangeType,
+}
+
+/// Changes of a namespace, yielded by [crate::conf::ApolloConfClient::watch_changes].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChangeEvent {
+    pub namespace_name: String,
+    /// Changes sorted by key.
+    pub changes: BTreeMap<String, ConfigChange>,
+}
+
+impl ConfigChangeEvent {
+    /// Compute the changes from older configurations to newer configurations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_client::conf::changes::{ChangeType, ConfigChangeEvent};
+    /// use std::collections::HashMap;
+    ///
+    /// let older = HashMap::from([("timeout".to_string(), "100".to_string())]);
+    /// let newer = HashMap::from([("timeout".to_string(), "200".to_string())]);
+    /// let event = ConfigChangeEvent::diff("application", &older, &newer);
+    /// assert_eq!(event.changes["timeout"].change_type, ChangeType::Modified);
+    /// ```
+    pub fn diff(
+        namespace_name: impl Into<String>,
+        older: &HashMap<String, String>,
+        newer: &HashMap<String, String>,
+    ) -> Self {
+        let mut changes = BTreeMap::new();
+
+        for (key, new_value) in newer {
+            let change_type = match older.get(key) {
+                None => ChangeType::Added,
+                Some(old_value) if old_value != new_value => ChangeType::Modified,
+                Some(_) => continue,
+            };
+            changes.insert(
+                key.clone(),
+                ConfigChange {
+                    key: key.clone(),
+                    old_value: older.get(key).cloned(),
+                    new_value: Some(new_value.clone()),
+                    change_type,
+                },
+            );
+        }
+
+        for (key, old_value) in older {
+            if !newer.contains_key(key) {
+                changes.insert(
+                    key.clone(),
+                    ConfigChange {
+                        key: key.clone(),
+                        old_value: Some(old_value.clone()),
+                        new_value: None,
+                        change_type: ChangeType::Deleted,
+                    },
+                );
+            }
+        }
+
+        Self {
+            namespace_name: namespace_name.into(),
+            changes,
+        }
+    }
+
+    /// Whether there are no changes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether the key is changed.
+    #[inline]
+    pub fn is_changed(&self, key: &str) -> bool {
+        self.changes.contains_key(key)
+    }
+
+    /// The changed keys, sorted.
+    pub fn changed_keys(&self) -> impl Iterator<Item = &str> {
+        self.changes.keys().map(|key| key.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configurations(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let older = configurations(&[("a", "1"), ("b", "2"), ("c", "3")]);
+        let newer = configurations(&[("a", "1"), ("b", "20"), ("d", "4")]);
+        let event = ConfigChangeEvent::diff("application", &older, &newer);
+
+        assert_eq!(event.namespace_name, "application");
+        assert_eq!(event.changed_keys().collect::<Vec<_>>(), ["b", "c", "d"]);
+        assert!(!event.is_changed("a"));
+        assert_eq!(
+            event.changes["b"],
+            ConfigChange {
+                key: "b".to_string(),
+                old_value: Some("2".to_string()),
+                new_value: Some("20".to_string()),
+                change_type: ChangeType::Modified,
+            }
+        );
+        assert_eq!(
+            event.changes["c"],
+            ConfigChange {
+                key: "c".to_string(),
+                old_value: Some("3".to_string()),
+                new_value: None,
+                change_type: ChangeType::Deleted,
+            }
+        );
+        assert_eq!(
+            event.changes["d"],
+            ConfigChange {
+                key: "d".to_string(),
+                old_value: None,
+                new_value: Some("4".to_string()),
+                change_type: ChangeType::Added,
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let older = configurations(&[("a", "1")]);
+        assert!(ConfigChangeEvent::diff("application", &older, &older.clone()).is_empty());
+        assert!(
+            ConfigChangeEvent::diff("application", &HashMap::new(), &HashMap::new()).is_empty()
+        );
+    }
+}
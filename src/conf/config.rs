@@ -0,0 +1,167 @@
+//! High-level configuration handle, holds the latest configurations in memory.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use apollo_client::conf::{
+//!     config::ApolloConfig, meta::IpValue, requests::WatchRequest, ApolloConfClientBuilder,
+//! };
+//! use std::error::Error;
+//! use url::Url;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn Error>> {
+//!     let client =
+//!         ApolloConfClientBuilder::new_via_config_service(Url::parse("http://localhost:8080")?)?
+//!             .build()?;
+//!
+//!     // Fetch all namespaces, then keep watching them in background.
+//!     let config = ApolloConfig::new(
+//!         client,
+//!         WatchRequest {
+//!             app_id: "SampleApp".to_string(),
+//!             namespace_names: vec!["application".into(), "application.json".into()],
+//!             ip: Some(IpValue::HostName),
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .await;
+//!
+//!     // Never block on the network.
+//!     let timeout = config.get("application", "timeout");
+//!     dbg!(timeout);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    conf::{requests::WatchRequest, responses::FetchResponse, ApolloConfClient},
+    utils::canonicalize_namespace,
+};
+use futures_util::{pin_mut, StreamExt};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+type Namespaces = HashMap<String, FetchResponse>;
+
+struct Inner {
+    namespaces: Arc<RwLock<Namespaces>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Configuration handle, watch the namespaces in a background task, and hold the latest
+/// configuration of each namespace in memory.
+///
+/// The background task is stopped when all the clones of the handle are dropped.
+#[derive(Clone)]
+pub struct ApolloConfig {
+    inner: Arc<Inner>,
+}
+
+impl ApolloConfig {
+    /// Create the handle, return after all namespaces are fetched first time, the namespaces
+    /// failed to fetch are logged and absent until fetched successfully.
+    ///
+    /// Must be called in the context of tokio runtime.
+    pub async fn new(client: ApolloConfClient, request: WatchRequest) -> Self {
+        let namespaces = Arc::new(RwLock::new(Namespaces::new()));
+        let (initialized_tx, initialized_rx) = oneshot::channel();
+
+        let task = tokio::spawn(Self::watch(
+            client,
+            request,
+            namespaces.clone(),
+            initialized_tx,
+        ));
+
+        // The watch stream always yields the first fetching, unless the task is panicked.
+        let _ = initialized_rx.await;
+
+        Self {
+            inner: Arc::new(Inner { namespaces, task }),
+        }
+    }
+
+    async fn watch(
+        client: ApolloConfClient,
+        request: WatchRequest,
+        namespaces: Arc<RwLock<Namespaces>>,
+        initialized_tx: oneshot::Sender<()>,
+    ) {
+        let stream = client.watch(request);
+        pin_mut!(stream);
+
+        let mut initialized_tx = Some(initialized_tx);
+        while let Some(responses) = stream.next().await {
+            match responses {
+                Ok(responses) => {
+                    let mut namespaces = namespaces.write().unwrap();
+                    for (namespace_name, response) in responses {
+                        match response {
+                            Ok(response) => {
+                                namespaces
+                                    .insert(canonicalize_namespace(&namespace_name), response);
+                            }
+                            Err(e) => {
+                                log::warn!("fetch namespace `{}` failed: {}", namespace_name, e);
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::warn!("watch namespaces failed: {}", e),
+            }
+
+            if let Some(initialized_tx) = initialized_tx.take() {
+                let _ = initialized_tx.send(());
+            }
+        }
+    }
+
+    /// Get the configuration value of the key in the namespace.
+    pub fn get(&self, namespace_name: &str, key: &str) -> Option<String> {
+        self.read(namespace_name, |response| {
+            response.configurations.get(key).cloned()
+        })
+        .flatten()
+    }
+
+    /// Get all the configurations of the namespace.
+    pub fn configurations(&self, namespace_name: &str) -> Option<HashMap<String, String>> {
+        self.read(namespace_name, |response| response.configurations.clone())
+    }
+
+    /// Get the release key of the namespace.
+    pub fn release_key(&self, namespace_name: &str) -> Option<String> {
+        self.read(namespace_name, |response| response.release_key.clone())
+    }
+
+    /// Get the names of the namespaces fetched successfully.
+    pub fn namespace_names(&self) -> Vec<String> {
+        self.inner
+            .namespaces
+            .read()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn read<T>(&self, namespace_name: &str, f: impl FnOnce(&FetchResponse) -> T) -> Option<T> {
+        self.inner
+            .namespaces
+            .read()
+            .unwrap()
+            .get(&canonicalize_namespace(namespace_name))
+            .map(f)
+    }
+}
@@ -0,0 +1,679 @@
+//! Deserialize the configurations of a namespace into user structs.
+//!
+//! For properties namespaces, the dotted keys like `db.pool.max` are mapped onto nested structs,
+//! the keys like `hosts[0]` are mapped onto sequences, and the string values are coerced into
+//! the target types, such as numbers, bools and [std::time::Duration].
+//!
+//! # Example
+//!
+//! ```
+//! use apollo_client::conf::de::from_configurations;
+//! use serde::Deserialize;
+//! use std::{collections::HashMap, time::Duration};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     db: Db,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Db {
+//!     pool: Pool,
+//!     timeout: Duration,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Pool {
+//!     max: u32,
+//!     enabled: bool,
+//! }
+//!
+//! let configurations = HashMap::from([
+//!     ("db.pool.max".to_string(), "10".to_string()),
+//!     ("db.pool.enabled".to_string(), "true".to_string()),
+//!     ("db.timeout".to_string(), "1500ms".to_string()),
+//! ]);
+//!
+//! let config: Config = from_configurations(&configurations).unwrap();
+//! assert_eq!(config.db.pool.max, 10);
+//! assert!(config.db.pool.enabled);
+//! assert_eq!(config.db.timeout, Duration::from_millis(1500));
+//! ```
+
+use crate::{
+    conf::responses::FetchResponse,
+    errors::{ApolloClientError, ApolloClientResult},
+    meta::NamespaceKind,
+    properties::Properties,
+};
+use serde::de::{
+    self,
+    value::{MapDeserializer, StrDeserializer},
+    DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor,
+};
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+/// Error occurred when deserializing configurations.
+#[derive(thiserror::Error, Debug)]
+#[error("{0}")]
+pub struct DeError(String);
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Deserialize the configurations of a properties namespace.
+pub fn from_configurations<'a, T, I>(configurations: I) -> Result<T, DeError>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (&'a String, &'a String)>,
+{
+    let mut root = Node::default();
+    for (key, value) in configurations {
+        root.insert(key, value);
+    }
+    T::deserialize(NodeDeserializer {
+        node: root,
+        path: String::new(),
+    })
+}
+
+/// Deserialize the properties returned by [crate::conf::ApolloConfClient::cached_fetch].
+pub fn from_properties<T: DeserializeOwned>(properties: &Properties) -> Result<T, DeError> {
+    let pairs = properties
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<Vec<_>>();
+    from_configurations(pairs.iter().map(|(key, value)| (key, value)))
+}
+
+/// Deserialize the `content` of a `.json` or `.yaml` namespace in the matching format, others
+/// are deserialized as properties namespace.
+///
+/// The `.yaml` namespace requires the feature `yaml`, otherwise
+/// [ApolloClientError::FeatureRequired] is returned.
+pub fn from_namespace<'a, T, I>(namespace_name: &str, configurations: I) -> ApolloClientResult<T>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (&'a String, &'a String)>,
+{
+    let content = |configurations: I| {
+        configurations
+            .into_iter()
+            .find(|(key, _)| key.as_str() == "content")
+            .map(|(_, value)| value)
+            .ok_or_else(|| ApolloClientError::MissingContent(namespace_name.to_string()))
+    };
+
+    match NamespaceKind::infer_namespace_kind(namespace_name) {
+        NamespaceKind::Json => Ok(serde_json::from_str(content(configurations)?)?),
+        #[cfg(feature = "yaml")]
+        NamespaceKind::Yaml => Ok(serde_yaml::from_str(content(configurations)?)?),
+        #[cfg(not(feature = "yaml"))]
+        NamespaceKind::Yaml => Err(ApolloClientError::FeatureRequired(
+            namespace_name.to_string(),
+            "yaml",
+        )),
+        _ => Ok(from_configurations(configurations)?),
+    }
+}
+
+impl FetchResponse {
+    /// Deserialize the configurations into `T`, see [from_namespace].
+    pub fn deserialize<T: DeserializeOwned>(&self) -> ApolloClientResult<T> {
+        from_namespace(&self.namespace_name, &self.configurations)
+    }
+}
+
+/// Tree of the dotted keys.
+#[derive(Debug, Default, Clone)]
+struct Node {
+    value: Option<String>,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, key: &str, value: &str) {
+        let mut node = self;
+        for segment in key.split('.') {
+            // Split `hosts[0][1]` into `hosts`, `0` and `1`.
+            let mut parts = segment.split('[');
+            let name = parts.next().unwrap_or_default();
+            node = node.children.entry(name.to_string()).or_default();
+            for index in parts {
+                node = node
+                    .children
+                    .entry(index.trim_end_matches(']').to_string())
+                    .or_default();
+            }
+        }
+        node.value = Some(value.to_string());
+    }
+}
+
+struct NodeDeserializer {
+    node: Node,
+    path: String,
+}
+
+impl NodeDeserializer {
+    fn leaf(value: String, path: String) -> Self {
+        Self {
+            node: Node {
+                value: Some(value),
+                children: Default::default(),
+            },
+            path,
+        }
+    }
+
+    fn value(&self) -> Result<&str, DeError> {
+        self.node
+            .value
+            .as_deref()
+            .ok_or_else(|| DeError(format!("key `{}` should be a value", self.path)))
+    }
+
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, DeError>
+    where
+        T::Err: fmt::Display,
+    {
+        let value = self.value()?;
+        value.trim().parse().map_err(|e| {
+            DeError(format!(
+                "invalid value `{}` of key `{}`: {}",
+                value, self.path, e
+            ))
+        })
+    }
+
+    fn into_map(self) -> NodeMapAccess {
+        let path = self.path;
+        NodeMapAccess {
+            children: self.node.children.into_iter(),
+            value: None,
+            path,
+        }
+    }
+
+    fn into_seq(self) -> Result<NodeSeqAccess, DeError> {
+        let path = self.path.clone();
+        let elements = if !self.node.children.is_empty() {
+            let mut elements = self
+                .node
+                .children
+                .into_iter()
+                .map(|(key, node)| match key.parse::<usize>() {
+                    Ok(index) => Ok((index, node)),
+                    Err(_) => Err(DeError(format!(
+                        "key `{}.{}` should be a sequence index",
+                        path, key
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            elements.sort_by_key(|(index, _)| *index);
+            elements.into_iter().map(|(_, node)| node).collect()
+        } else {
+            // Comma separated values.
+            let value = self.value()?;
+            if value.trim().is_empty() {
+                vec![]
+            } else {
+                value
+                    .split(',')
+                    .map(|item| Node {
+                        value: Some(item.trim().to_string()),
+                        children: Default::default(),
+                    })
+                    .collect()
+            }
+        };
+        Ok(NodeSeqAccess {
+            elements: elements.into_iter().enumerate(),
+            path,
+        })
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.node.value {
+            Some(value) if self.node.children.is_empty() => visitor.visit_string(value),
+            _ => visitor.visit_map(self.into_map()),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.value()?;
+        match value.trim().to_ascii_lowercase().as_str() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => Err(DeError(format!(
+                "invalid value `{}` of key `{}`: expected `true` or `false`",
+                value, self.path
+            ))),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.value()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_bytes(self.value()?.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self.node.value {
+            Some(value) if value.is_empty() && self.node.children.is_empty() => {
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self.into_seq()?)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self.into_map())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Coerce the string value like `30s` into `std::time::Duration`.
+        if name == "Duration" && fields == ["secs", "nanos"] && self.node.children.is_empty() {
+            let value = self.value()?;
+            let duration = parse_duration(value).ok_or_else(|| {
+                DeError(format!(
+                    "invalid value `{}` of key `{}`: expected duration like `30s`",
+                    value, self.path
+                ))
+            })?;
+            return visitor.visit_map(MapDeserializer::<_, DeError>::new(
+                [
+                    ("secs", duration.as_secs()),
+                    ("nanos", duration.subsec_nanos() as u64),
+                ]
+                .into_iter(),
+            ));
+        }
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self.value()?;
+        visitor.visit_enum(StrDeserializer::<DeError>::new(value.trim()))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct NodeMapAccess {
+    children: std::collections::btree_map::IntoIter<String, Node>,
+    value: Option<(String, Node)>,
+    path: String,
+}
+
+impl<'de> MapAccess<'de> for NodeMapAccess {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.children.next() {
+            Some((key, node)) => {
+                let path = if self.path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", self.path, key)
+                };
+                let key_deserializer = NodeDeserializer::leaf(key, path.clone());
+                self.value = Some((path, node));
+                seed.deserialize(key_deserializer).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (path, node) = self
+            .value
+            .take()
+            .ok_or_else(|| DeError("value is missing".to_string()))?;
+        seed.deserialize(NodeDeserializer { node, path })
+    }
+}
+
+struct NodeSeqAccess {
+    elements: std::iter::Enumerate<std::vec::IntoIter<Node>>,
+    path: String,
+}
+
+impl<'de> SeqAccess<'de> for NodeSeqAccess {
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.elements.next() {
+            Some((index, node)) => {
+                let path = format!("{}[{}]", self.path, index);
+                seed.deserialize(NodeDeserializer { node, path }).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Parse the duration like `100ms`, `30s`, `1h30m`, the plain number is treated as
+/// milliseconds, same as spring.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if let Ok(millis) = s.parse::<u64>() {
+        return Some(Duration::from_millis(millis));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number = rest[..number_len].parse::<f64>().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = rest[..unit_len].trim();
+        rest = &rest[unit_len..];
+
+        let seconds = match unit {
+            "ns" => number / 1e9,
+            "us" => number / 1e6,
+            "ms" => number / 1e3,
+            "s" => number,
+            "m" => number * 60.,
+            "h" => number * 60. * 60.,
+            "d" => number * 60. * 60. * 24.,
+            _ => return None,
+        };
+        total = total.checked_add(try_from_secs_f64(seconds)?)?;
+    }
+    Some(total)
+}
+
+/// Same as `Duration::try_from_secs_f64`, which requires rust 1.66.
+fn try_from_secs_f64(seconds: f64) -> Option<Duration> {
+    if (0. ..u64::MAX as f64).contains(&seconds) {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    fn configurations(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        name: String,
+        port: u16,
+        ratio: f64,
+        debug: bool,
+        timeout: Duration,
+        mode: Mode,
+        hosts: Vec<String>,
+        tags: Vec<String>,
+        limits: HashMap<String, u32>,
+        optional: Option<u32>,
+        empty: Option<u32>,
+        db: Db,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Db {
+        pool: Pool,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Pool {
+        max: usize,
+    }
+
+    #[test]
+    fn test_from_configurations() {
+        let config: Config = from_configurations(&configurations(&[
+            ("name", "app"),
+            ("port", "8080"),
+            ("ratio", "0.5"),
+            ("debug", "TRUE"),
+            ("timeout", "1m30s"),
+            ("mode", "safe"),
+            ("hosts[1]", "b"),
+            ("hosts[0]", "a"),
+            ("tags", "x, y"),
+            ("limits.foo", "1"),
+            ("limits.bar", "2"),
+            ("empty", ""),
+            ("db.pool.max", "10"),
+            ("unknown.key", "ignored"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                name: "app".to_string(),
+                port: 8080,
+                ratio: 0.5,
+                debug: true,
+                timeout: Duration::from_secs(90),
+                mode: Mode::Safe,
+                hosts: vec!["a".to_string(), "b".to_string()],
+                tags: vec!["x".to_string(), "y".to_string()],
+                limits: HashMap::from([("foo".to_string(), 1), ("bar".to_string(), 2)]),
+                optional: None,
+                empty: None,
+                db: Db {
+                    pool: Pool { max: 10 },
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_configurations_invalid() {
+        let result = from_configurations::<Db, _>(&configurations(&[("db.pool.max", "ten")]));
+        assert!(result.is_err());
+
+        let result = from_configurations::<Pool, _>(&configurations(&[("max", "ten")]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid value `ten` of key `max`: invalid digit found in string"
+        );
+
+        #[derive(Debug, Deserialize)]
+        struct Timeout {
+            #[allow(dead_code)]
+            timeout: Duration,
+        }
+        let result = from_configurations::<Timeout, _>(&configurations(&[(
+            "timeout",
+            "99999999999999999999h",
+        )]));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid value `99999999999999999999h` of key `timeout`: expected duration like `30s`"
+        );
+    }
+
+    #[test]
+    fn test_from_namespace() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Timeout {
+            timeout: u32,
+        }
+
+        let timeout: Timeout = from_namespace(
+            "application.json",
+            &configurations(&[("content", r#"{"timeout": 100}"#)]),
+        )
+        .unwrap();
+        assert_eq!(timeout, Timeout { timeout: 100 });
+
+        #[cfg(feature = "yaml")]
+        {
+            let timeout: Timeout = from_namespace(
+                "application.yml",
+                &configurations(&[("content", "timeout: 200")]),
+            )
+            .unwrap();
+            assert_eq!(timeout, Timeout { timeout: 200 });
+        }
+        #[cfg(not(feature = "yaml"))]
+        assert!(matches!(
+            from_namespace::<Timeout, _>(
+                "application.yml",
+                &configurations(&[("content", "timeout: 200")]),
+            ),
+            Err(ApolloClientError::FeatureRequired(_, "yaml"))
+        ));
+
+        let timeout: Timeout =
+            from_namespace("application", &configurations(&[("timeout", "300")])).unwrap();
+        assert_eq!(timeout, Timeout { timeout: 300 });
+
+        assert!(matches!(
+            from_namespace::<Timeout, _>("application.json", &HashMap::new()),
+            Err(ApolloClientError::MissingContent(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1500"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("100ms"), Some(Duration::from_millis(100)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2d"), Some(Duration::from_secs(172800)));
+        assert_eq!(parse_duration("30x"), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("99999999999999999999h"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+    }
+}
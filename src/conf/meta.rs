@@ -7,7 +7,10 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    collections::{hash_map::RandomState, HashMap},
     fmt::{self, Display},
+    hash::{BuildHasher, Hasher},
+    time::Duration,
 };
 
 pub(crate) const UNINITIALIZED_NOTIFICATION_ID: i32 = -1;
@@ -18,6 +21,10 @@ pub(crate) const UNINITIALIZED_NOTIFICATION_ID: i32 = -1;
 pub struct Notification {
     pub namespace_name: String,
     pub notification_id: i32,
+    /// Release messages of the notification response, forwarded to the fetching, to make sure
+    /// the config service returns the latest (or gray) release.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<ApolloNotificationMessages>,
 }
 
 impl Default for Notification {
@@ -25,6 +32,7 @@ impl Default for Notification {
         Self {
             namespace_name: "".to_string(),
             notification_id: UNINITIALIZED_NOTIFICATION_ID,
+            messages: None,
         }
     }
 }
@@ -63,13 +71,41 @@ impl Notification {
     ) -> Vec<FetchRequest> {
         notifications
             .into_iter()
-            .map(|notification| FetchRequest::from_watch(watch, notification.namespace_name))
+            .map(|notification| FetchRequest {
+                messages: notification.messages,
+                ..FetchRequest::from_watch(watch, notification.namespace_name)
+            })
             .collect()
     }
 }
 
 implement_json_perform_response_for! { Vec<Notification> }
 
+/// Release messages of notification, the key of details is `{app_id}+{cluster}+{namespace}`,
+/// and the value is the id of release message.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApolloNotificationMessages {
+    pub details: HashMap<String, i64>,
+}
+
+impl ApolloNotificationMessages {
+    /// Whether there are no messages.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    /// Merge the other messages, keep the larger id of the same key.
+    pub fn merge_from(&mut self, other: &Self) {
+        for (key, id) in &other.details {
+            let entry = self.details.entry(key.clone()).or_insert(*id);
+            if *entry < *id {
+                *entry = *id;
+            }
+        }
+    }
+}
+
 /// Apollo config api `ip` param value.
 #[derive(Debug, Clone, PartialEq)]
 pub enum IpValue {
@@ -152,11 +188,133 @@ impl Display for IpValue {
     }
 }
 
+/// Retry policy of the requests when apollo is unavailable, the delay between the attempts grows
+/// exponentially from `base_delay` up to `max_delay`.
+///
+/// Applied to [crate::conf::ApolloConfClient::fetch] and
+/// [crate::conf::ApolloConfClient::cached_fetch], and as the backoff between failed long polls
+/// of [crate::conf::ApolloConfClient::watch], which never stops retrying.
+///
+/// The default policy is 3 attempts, delay from 1 second to 120 seconds like the java client,
+/// with 20% jitter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Max attempts of a request including the first one, `1` means no retry.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Upper limit of the delay.
+    pub max_delay: Duration,
+    /// Fraction in `0.0..=1.0` of the delay to be randomly reduced, to avoid clients retrying
+    /// at the same time.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(120),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy never retry the requests, the long polls of watching still back off with the
+    /// default delay.
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before the retry, `retries` is the count of retries already done.
+    pub fn delay(&self, retries: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .checked_mul(2u32.saturating_pow(retries))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+        delay.mul_f64(1.0 - jitter)
+    }
+}
+
+/// Random number in `0.0..1.0`, good enough for jitter, avoid depending on `rand`.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn test_notification_messages() {
+        let notifications: Vec<Notification> = serde_json::from_str(
+            r#"[{"namespaceName":"application","notificationId":101,"messages":{"details":{"SampleApp+default+application":101}}},{"namespaceName":"foo","notificationId":102}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            notifications[0].messages.as_ref().unwrap().details["SampleApp+default+application"],
+            101
+        );
+        assert_eq!(notifications[1].messages, None);
+
+        // Messages are not sent by the long poll request.
+        assert_eq!(
+            serde_json::to_string(&Notification {
+                namespace_name: "foo".to_string(),
+                ..Default::default()
+            })
+            .unwrap(),
+            r#"{"namespaceName":"foo","notificationId":-1}"#
+        );
+
+        let mut messages = ApolloNotificationMessages {
+            details: HashMap::from([("a".to_string(), 1), ("b".to_string(), 5)]),
+        };
+        messages.merge_from(&ApolloNotificationMessages {
+            details: HashMap::from([("b".to_string(), 3), ("c".to_string(), 2)]),
+        });
+        assert_eq!(
+            messages.details,
+            HashMap::from([
+                ("a".to_string(), 1),
+                ("b".to_string(), 5),
+                ("c".to_string(), 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+        let delays = (0..9).map(|retries| policy.delay(retries).as_secs());
+        assert_eq!(
+            delays.collect::<Vec<_>>(),
+            [1, 2, 4, 8, 16, 32, 64, 120, 120]
+        );
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(120));
+
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            let delay = policy.delay(2);
+            assert!(delay > Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        }
+    }
+
     #[test]
     fn test_notification_new() {
         let notification = Notification {
@@ -169,6 +327,7 @@ mod tests {
         let notification = Notification {
             namespace_name: "foo.yaml".to_string(),
             notification_id: 10,
+            ..Default::default()
         };
         assert_eq!(notification.namespace_name, "foo.yaml");
         assert_eq!(notification.notification_id, 10);
@@ -184,6 +343,7 @@ mod tests {
             Notification {
                 namespace_name: "bar".to_string(),
                 notification_id: 10,
+                ..Default::default()
             },
         ];
         Notification::update_notifications(
@@ -191,6 +351,7 @@ mod tests {
             &[Notification {
                 namespace_name: "foo".to_string(),
                 notification_id: 100,
+                ..Default::default()
             }],
         );
         assert_eq!(
@@ -199,10 +360,12 @@ mod tests {
                 Notification {
                     namespace_name: "foo".to_string(),
                     notification_id: 100,
+                    ..Default::default()
                 },
                 Notification {
                     namespace_name: "bar".to_string(),
                     notification_id: 10,
+                    ..Default::default()
                 },
             ]
         );
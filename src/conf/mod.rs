@@ -10,8 +10,8 @@
 //! use apollo_client::{
 //!     conf::{meta::IpValue, requests::CachedFetchRequest, ApolloConfClientBuilder},
 //!     errors::ApolloClientResult,
+//!     properties::Properties,
 //! };
-//! use ini::Properties;
 //! use std::error::Error;
 //! use url::Url;
 //!
@@ -86,44 +86,72 @@
 //! }
 //! ```
 
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+mod cache;
+pub mod changes;
+pub mod config;
+pub mod de;
 pub mod meta;
+pub mod parse;
 pub mod requests;
 pub mod responses;
+mod services;
+pub mod watch;
 
 use crate::{
     conf::{
-        meta::Notification,
+        cache::LocalCache,
+        changes::ConfigChangeEvent,
+        meta::{Notification, RetryPolicy},
         requests::{
-            CachedFetchRequest, FetchRequest, NotifyRequest, PerformConfRequest, WatchRequest,
+            CachedFetchRequest, ConfigServicesRequest, FetchRequest, NotifyRequest,
+            PerformConfRequest, WatchRequest,
         },
-        responses::FetchResponse,
+        responses::{FetchResponse, ServiceResponse},
+        services::ConfigServices,
+        watch::{WatchHandle, WatchShared},
     },
-    errors::{ApolloClientError::ApolloResponse, ApolloClientResult},
-    meta::{
-        handle_url, validate_response, PerformResponse, DEFAULT_NOTIFY_TIMEOUT, DEFAULT_TIMEOUT,
+    errors::{
+        ApolloClientError::{self, ApolloResponse},
+        ApolloClientResult,
     },
+    meta::{build_request, validate_response, PerformResponse, DEFAULT_NOTIFY_TIMEOUT},
+    properties::Properties,
+    transport::HttpTransport,
 };
 use async_stream::stream;
 use futures_core::Stream;
-use futures_util::{stream, StreamExt};
+use futures_util::{
+    future::{select, Either},
+    lock::Mutex,
+    pin_mut, stream, StreamExt,
+};
 use http::status::StatusCode;
-use ini::Properties;
-use reqwest::{Client, ClientBuilder};
-use std::collections::HashMap;
+#[cfg(feature = "reqwest")]
+use reqwest::ClientBuilder;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 use url::Url;
 
+/// Default interval to re-discover config services via meta server, same as the java client.
+const DEFAULT_DISCOVERY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 #[derive(Clone)]
 enum ServerUrl {
-    ConfigServer(Url),
-    /// Todo implement fetch config via meta server.
-    #[allow(dead_code)]
+    ConfigServers(Vec<Url>),
     MetaServer(Url),
 }
 
 /// Builder for [ApolloConfClient].
 pub struct ApolloConfClientBuilder {
     server_url: ServerUrl,
+    #[cfg(feature = "reqwest")]
     client_builder: ClientBuilder,
+    transport: Option<Arc<dyn HttpTransport>>,
+    discovery_interval: Duration,
+    cache: Option<LocalCache>,
+    retry_policy: RetryPolicy,
 }
 
 impl ApolloConfClientBuilder {
@@ -141,15 +169,66 @@ impl ApolloConfClientBuilder {
     /// .unwrap();
     /// ```
     pub fn new_via_config_service(config_server_url: Url) -> ApolloClientResult<Self> {
-        let mut builder = Self {
-            server_url: ServerUrl::ConfigServer(config_server_url),
-            client_builder: Default::default(),
-        };
-        builder.client_builder = builder.client_builder.timeout(DEFAULT_TIMEOUT);
-        Ok(builder)
+        Self::new_via_config_services([config_server_url])
     }
 
-    /// Customize inner http client.
+    /// Create a client request api via multi config services, the requests are spread across
+    /// them, and retried on the next one when a config service is unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_client::conf::ApolloConfClientBuilder;
+    /// use url::Url;
+    ///
+    /// let _builder = ApolloConfClientBuilder::new_via_config_services([
+    ///     Url::parse("http://localhost:8080").unwrap(),
+    ///     Url::parse("http://localhost:8081").unwrap(),
+    /// ])
+    /// .unwrap();
+    /// ```
+    pub fn new_via_config_services(
+        config_server_urls: impl IntoIterator<Item = Url>,
+    ) -> ApolloClientResult<Self> {
+        let config_server_urls = config_server_urls.into_iter().collect::<Vec<_>>();
+        if config_server_urls.is_empty() {
+            return Err(ApolloClientError::NoConfigService);
+        }
+        Ok(Self::new(ServerUrl::ConfigServers(config_server_urls)))
+    }
+
+    /// Create a client request api via meta server, the config service instances are
+    /// discovered from the meta server `/services/config` api, and re-discovered periodically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_client::conf::ApolloConfClientBuilder;
+    /// use url::Url;
+    ///
+    /// let _builder = ApolloConfClientBuilder::new_via_meta_server(
+    ///     Url::parse("http://localhost:8080").unwrap(),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn new_via_meta_server(meta_server_url: Url) -> ApolloClientResult<Self> {
+        Ok(Self::new(ServerUrl::MetaServer(meta_server_url)))
+    }
+
+    fn new(server_url: ServerUrl) -> Self {
+        Self {
+            server_url,
+            #[cfg(feature = "reqwest")]
+            client_builder: ClientBuilder::default().timeout(crate::meta::DEFAULT_TIMEOUT),
+            transport: None,
+            discovery_interval: DEFAULT_DISCOVERY_INTERVAL,
+            cache: None,
+            retry_policy: Default::default(),
+        }
+    }
+
+    /// Customize inner http client, take no effect if [ApolloConfClientBuilder::with_transport]
+    /// is called.
     ///
     /// # Example
     ///
@@ -162,16 +241,90 @@ impl ApolloConfClientBuilder {
     ///     .unwrap()
     ///     .with_client_builder(|builder| builder.timeout(Duration::from_secs(6)));
     /// ```
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
     pub fn with_client_builder(mut self, f: impl FnOnce(ClientBuilder) -> ClientBuilder) -> Self {
         self.client_builder = f(self.client_builder);
         self
     }
 
+    /// Send the requests by the custom transport instead of the default reqwest client, see
+    /// [crate::transport].
+    pub fn with_transport(mut self, transport: impl HttpTransport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Customize the interval to re-discover config services, only affect the client created
+    /// by [ApolloConfClientBuilder::new_via_meta_server], default is 5 minutes.
+    pub fn with_discovery_interval(mut self, interval: Duration) -> Self {
+        self.discovery_interval = interval;
+        self
+    }
+
+    /// Enable the local disk cache, the configurations fetched by [ApolloConfClient::fetch] and
+    /// [ApolloConfClient::watch] are persisted to the directory, and loaded from it when apollo
+    /// is unreachable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_client::conf::ApolloConfClientBuilder;
+    /// use url::Url;
+    ///
+    /// ApolloConfClientBuilder::new_via_config_service(Url::parse("http://localhost:8080").unwrap())
+    ///     .unwrap()
+    ///     .with_cache_dir("/opt/data/SampleApp/config-cache");
+    /// ```
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(LocalCache::new(cache_dir.into()));
+        self
+    }
+
+    /// Customize the retry policy when apollo is unavailable, see [RetryPolicy].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_client::conf::{meta::RetryPolicy, ApolloConfClientBuilder};
+    /// use std::time::Duration;
+    /// use url::Url;
+    ///
+    /// ApolloConfClientBuilder::new_via_config_service(Url::parse("http://localhost:8080").unwrap())
+    ///     .unwrap()
+    ///     .with_retry_policy(RetryPolicy {
+    ///         max_attempts: 5,
+    ///         base_delay: Duration::from_millis(500),
+    ///         ..Default::default()
+    ///     });
+    /// ```
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Build the [ApolloConfClient].
     pub fn build(self) -> ApolloClientResult<ApolloConfClient> {
+        let services = match &self.server_url {
+            ServerUrl::ConfigServers(urls) => ConfigServices::new(urls.clone()),
+            ServerUrl::MetaServer(_) => Default::default(),
+        };
+        let transport: Arc<dyn HttpTransport> = match self.transport {
+            Some(transport) => transport,
+            #[cfg(feature = "reqwest")]
+            None => Arc::new(crate::transport::ReqwestTransport::new(
+                self.client_builder.build()?,
+            )),
+            #[cfg(not(feature = "reqwest"))]
+            None => return Err(ApolloClientError::NoTransport),
+        };
         Ok(ApolloConfClient {
             server_url: self.server_url,
-            client: self.client_builder.build()?,
+            transport,
+            discovery_interval: self.discovery_interval,
+            services: Arc::new(Mutex::new(services)),
+            cache: self.cache,
+            retry_policy: self.retry_policy,
         })
     }
 }
@@ -180,7 +333,11 @@ impl ApolloConfClientBuilder {
 #[derive(Clone)]
 pub struct ApolloConfClient {
     server_url: ServerUrl,
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
+    discovery_interval: Duration,
+    services: Arc<Mutex<ConfigServices>>,
+    cache: Option<LocalCache>,
+    retry_policy: RetryPolicy,
 }
 
 impl ApolloConfClient {
@@ -190,47 +347,164 @@ impl ApolloConfClient {
         &self,
         request: CachedFetchRequest,
     ) -> ApolloClientResult<Properties> {
-        self.execute(request).await
+        self.execute_with_retry(&request).await
     }
 
     /// 通过不带缓存的Http接口从Apollo读取配置。
     /// [Ref](https://www.apolloconfig.com/#/zh/usage/other-language-client-user-guide?id=_13-%e9%80%9a%e8%bf%87%e4%b8%8d%e5%b8%a6%e7%bc%93%e5%ad%98%e7%9a%84http%e6%8e%a5%e5%8f%a3%e4%bb%8eapollo%e8%af%bb%e5%8f%96%e9%85%8d%e7%bd%ae)
+    ///
+    /// If the local cache is enabled by [ApolloConfClientBuilder::with_cache_dir], the response
+    /// is persisted, and the cached one is returned when apollo is unreachable or timed out,
+    /// marked by [FetchResponse::from_cache].
     pub async fn fetch(&self, request: FetchRequest) -> ApolloClientResult<FetchResponse> {
-        self.execute(request).await
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.execute_with_retry(&request).await,
+        };
+
+        match self.execute_with_retry(&request).await {
+            Ok(response) => {
+                if let Err(e) = cache.save(&request, &response) {
+                    log::warn!("save local cache failed: {}", e);
+                }
+                Ok(response)
+            }
+            Err(e) if e.is_server_unavailable() => match cache.load(&request) {
+                Ok(Some(response)) => {
+                    log::warn!(
+                        "apollo is unreachable, use local cache of `{}`: {}",
+                        request.namespace_name,
+                        e
+                    );
+                    Ok(response)
+                }
+                Ok(None) => Err(e),
+                Err(cache_error) => {
+                    log::warn!("load local cache failed: {}", cache_error);
+                    Err(e)
+                }
+            },
+            Err(e) => Err(e),
+        }
     }
 
     /// 应用感知配置更新。
     /// [Ref](https://www.apolloconfig.com/#/zh/usage/other-language-client-user-guide?id=_14-%e5%ba%94%e7%94%a8%e6%84%9f%e7%9f%a5%e9%85%8d%e7%bd%ae%e6%9b%b4%e6%96%b0)
     pub async fn notify(&self, request: NotifyRequest) -> ApolloClientResult<Vec<Notification>> {
-        self.execute(request).await
+        self.execute(&request).await
+    }
+
+    /// Execute the request, and retry by the [RetryPolicy] when apollo is unavailable.
+    async fn execute_with_retry<R: PerformResponse>(
+        &self,
+        request: &impl PerformConfRequest<Response = R>,
+    ) -> ApolloClientResult<R> {
+        let mut retries = 0;
+        loop {
+            match self.execute(request).await {
+                Err(e)
+                    if e.is_server_unavailable()
+                        && retries + 1 < self.retry_policy.max_attempts =>
+                {
+                    let delay = self.retry_policy.delay(retries);
+                    log::warn!("apollo is unavailable, retry after {:?}: {}", delay, e);
+                    tokio::time::sleep(delay).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
     async fn execute<R: PerformResponse>(
         &self,
-        request: impl PerformConfRequest<Response = R>,
+        request: &impl PerformConfRequest<Response = R>,
     ) -> ApolloClientResult<R> {
-        let url = match &self.server_url {
-            ServerUrl::ConfigServer(url) => handle_url(&request, url.clone())?,
-            ServerUrl::MetaServer(_) => todo!("unreachable here now"),
-        };
-        let mut request_builder = self.client.request(request.method(), url);
-        request_builder = request.request_builder(request_builder);
-        let response = request_builder.send().await?;
-        let response = validate_response(response).await?;
-        <R>::from_response(response).await
+        let mut last_error = ApolloClientError::NoConfigService;
+        for base_url in self.config_service_urls().await? {
+            match self.execute_on(request, base_url.clone()).await {
+                Err(e) if e.is_server_unavailable() => {
+                    log::warn!(
+                        "config service `{}` is unavailable, try the next one: {}",
+                        base_url,
+                        e
+                    );
+                    self.services.lock().await.mark_unhealthy(&base_url);
+                    last_error = e;
+                }
+                result => return result,
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn execute_on<R: PerformResponse>(
+        &self,
+        request: &impl PerformConfRequest<Response = R>,
+        base_url: Url,
+    ) -> ApolloClientResult<R> {
+        let request = build_request(request, base_url)?;
+        let response = self.transport.send(request).await?;
+        let response = validate_response(response)?;
+        <R>::from_response(response)
+    }
+
+    /// Get the config service urls to try in order, the instances are re-discovered when expired
+    /// if the client is created via meta server.
+    async fn config_service_urls(&self) -> ApolloClientResult<Vec<Url>> {
+        let mut services = self.services.lock().await;
+
+        if let ServerUrl::MetaServer(meta_server_url) = &self.server_url {
+            if services.is_discovery_expired(self.discovery_interval) {
+                match self.discover_config_services(meta_server_url).await {
+                    Ok(urls) => services.update_discovered(urls),
+                    // Keep using the previously discovered instances.
+                    Err(e) if !services.is_empty() => {
+                        log::warn!(
+                            "discover config services failed, keep the stale ones: {}",
+                            e
+                        );
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        if services.is_empty() {
+            return Err(ApolloClientError::NoConfigService);
+        }
+        Ok(services.candidates())
+    }
+
+    async fn discover_config_services(
+        &self,
+        meta_server_url: &Url,
+    ) -> ApolloClientResult<Vec<Url>> {
+        let request = ConfigServicesRequest;
+        let request = build_request(&request, meta_server_url.clone())?;
+        let response = self.transport.send(request).await?;
+        let response = validate_response(response)?;
+        let services = Vec::<ServiceResponse>::from_response(response)?;
+        let urls = services
+            .into_iter()
+            .map(|service| Url::parse(&service.homepage_url))
+            .collect::<Result<Vec<_>, _>>()?;
+        log::debug!("discovered config services: {:?}", urls);
+        Ok(urls)
     }
 
     /// Watch the multi namespaces change, and fetch namespaces configuration when changed.
     ///
     /// Return the Stream implemented [futures_core::Stream], and the return value of `poll_next`
-    /// will never be None (Dead Loop).
+    /// will never be None (Dead Loop), use [ApolloConfClient::watch_with_handle] to stop it.
+    ///
+    /// The stream owns a clone of the client, so it can be moved into a spawned task.
     ///
     /// The first `poll_next` will fetch all namespaces, the remained will only fetch changed
     /// namespaces.
     ///
-    /// # Panic
-    ///
-    /// panic if `request.namespace_names` is empty.
+    /// The failed long polls are yielded as errors, and backed off by the delay of
+    /// [ApolloConfClientBuilder::with_retry_policy] before polling again.
     ///
     /// # Example
     ///
@@ -268,45 +542,219 @@ impl ApolloConfClient {
     pub fn watch(
         &self,
         request: WatchRequest,
-    ) -> impl Stream<Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>> + '_
-    {
-        let mut watch_notifications = request.create_notifications();
-        let mut fetch_notifications = watch_notifications.clone();
-        assert_ne!(
-            watch_notifications.len(),
-            0,
-            "watch namespaces should not be null"
-        );
+    ) -> impl Stream<Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>>
+           + 'static {
+        let shared = Arc::new(WatchShared::new(request.create_notifications()));
+        self.watch_shared(request, shared)
+    }
+
+    /// Watch the multi namespaces change like [ApolloConfClient::watch], and return the
+    /// [WatchHandle] to stop the stream, or subscribe and unsubscribe namespaces while
+    /// watching.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_client::conf::{requests::WatchRequest, ApolloConfClient};
+    /// use futures_util::stream::StreamExt;
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client: ApolloConfClient = todo!();
+    ///
+    ///     let (handle, stream) = client.watch_with_handle(WatchRequest {
+    ///         app_id: "SampleApp".to_string(),
+    ///         namespace_names: vec!["application.properties".into()],
+    ///         ..Default::default()
+    ///     });
+    ///
+    ///     let task = tokio::spawn(async move {
+    ///         let mut stream = Box::pin(stream);
+    ///         // Ends after the handle stopped.
+    ///         while let Some(response) = stream.next().await {
+    ///             let _ = dbg!(response);
+    ///         }
+    ///     });
+    ///
+    ///     // Watch the namespace of the module loaded at runtime.
+    ///     handle.subscribe("module.yaml");
+    ///
+    ///     tokio::signal::ctrl_c().await?;
+    ///     handle.stop();
+    ///     task.await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn watch_with_handle(
+        &self,
+        request: WatchRequest,
+    ) -> (
+        WatchHandle,
+        impl Stream<Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>>
+            + 'static,
+    ) {
+        let shared = Arc::new(WatchShared::new(request.create_notifications()));
+        let (stream, abort_handle) = stream::abortable(self.watch_shared(request, shared.clone()));
+        (WatchHandle::new(abort_handle, shared), stream)
+    }
+
+    fn watch_shared(
+        &self,
+        request: WatchRequest,
+        shared: Arc<WatchShared>,
+    ) -> impl Stream<Item = ApolloClientResult<HashMap<String, ApolloClientResult<FetchResponse>>>>
+           + 'static {
+        let client = self.clone();
 
         stream! {
+            let mut fetch_notifications = shared.take_pending();
+            // The first fetching is always yielded, even if no namespaces are watched.
+            let mut is_first = true;
+
             loop {
-                let requests = Notification::create_fetch_requests(fetch_notifications, &request);
-                yield Ok(self.fetch_multi(requests).await);
+                if is_first || !fetch_notifications.is_empty() {
+                    let requests = Notification::create_fetch_requests(fetch_notifications, &request);
+                    yield Ok(client.fetch_multi(requests).await);
+                    is_first = false;
+                }
+
+                let mut failures = 0;
+                fetch_notifications = loop {
+                    let changed = shared.changed.notified();
+                    pin_mut!(changed);
 
-                loop {
-                    match self
-                        .execute(NotifyRequest::from_watch(
+                    let notifications = shared.notifications();
+                    if notifications.is_empty() {
+                        // Idle until namespaces subscribed.
+                        changed.await;
+                    } else {
+                        let notify_request = NotifyRequest::from_watch(
                             &request,
-                            watch_notifications.clone(),
+                            notifications,
                             DEFAULT_NOTIFY_TIMEOUT,
-                        ))
-                        .await
-                    {
-                        Ok(notifications) => {
-                            let is_uninitialized = watch_notifications[0].is_uninitialized();
-                            Notification::update_notifications(
-                                &mut watch_notifications,
-                                &notifications,
+                        );
+                        let notify = client.execute(&notify_request);
+                        pin_mut!(notify);
+
+                        // Interrupt the long poll when the watched namespaces changed.
+                        let result = match select(notify, changed.as_mut()).await {
+                            Either::Left((result, _)) => Some(result),
+                            Either::Right(_) => None,
+                        };
+
+                        match result {
+                            Some(Ok(notifications)) => {
+                                failures = 0;
+                                let notifications = shared.update(&notifications);
+                                if !notifications.is_empty() {
+                                    break notifications;
+                                }
+                            },
+                            Some(Err(ApolloResponse(e))) if e.status == StatusCode::NOT_MODIFIED => {
+                                failures = 0;
+                            },
+                            Some(Err(e)) => {
+                                let delay = client.retry_policy.delay(failures);
+                                failures = failures.saturating_add(1);
+                                yield Err(e);
+                                let sleep = tokio::time::sleep(delay);
+                                pin_mut!(sleep);
+                                select(sleep, changed).await;
+                            },
+                            None => {},
+                        }
+                    }
+
+                    let pending = shared.take_pending();
+                    if !pending.is_empty() {
+                        break pending;
+                    }
+                };
+            }
+        }
+    }
+
+    /// Watch the multi namespaces change like [ApolloConfClient::watch], and yield the changed
+    /// keys of each namespace, by diffing against the previously fetched configurations.
+    ///
+    /// The first fetching of namespaces is treated as the baseline, so no events are yielded for
+    /// it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_client::conf::{requests::WatchRequest, ApolloConfClient};
+    /// use futures_util::{pin_mut, stream::StreamExt};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client: ApolloConfClient = todo!();
+    ///
+    ///     let stream = client.watch_changes(WatchRequest {
+    ///         app_id: "SampleApp".to_string(),
+    ///         namespace_names: vec!["application.properties".into()],
+    ///         ..Default::default()
+    ///     });
+    ///
+    ///     pin_mut!(stream);
+    ///
+    ///     while let Some(event) = stream.next().await {
+    ///         let event = event?;
+    ///         if event.is_changed("db.pool.max") {
+    ///             // Reload the db pool.
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn watch_changes(
+        &self,
+        request: WatchRequest,
+    ) -> impl Stream<Item = ApolloClientResult<ConfigChangeEvent>> + 'static {
+        let watch_stream = self.watch(request);
+
+        stream! {
+            pin_mut!(watch_stream);
+
+            let mut namespaces = HashMap::<String, HashMap<String, String>>::new();
+            let mut is_baseline = true;
+
+            while let Some(responses) = watch_stream.next().await {
+                let responses = match responses {
+                    Ok(responses) => responses,
+                    Err(e) => {
+                        yield Err(e);
+                        continue;
+                    }
+                };
+
+                for (namespace_name, response) in responses {
+                    match response {
+                        Ok(response) => {
+                            let older = namespaces
+                                .insert(namespace_name.clone(), response.configurations)
+                                .unwrap_or_default();
+                            if is_baseline {
+                                continue;
+                            }
+                            let event = ConfigChangeEvent::diff(
+                                namespace_name.clone(),
+                                &older,
+                                &namespaces[&namespace_name],
                             );
-                            fetch_notifications = notifications;
-                            if !is_uninitialized {
-                                break;
+                            if !event.is_empty() {
+                                yield Ok(event);
                             }
-                        },
-                        Err(ApolloResponse(e)) if e.status == StatusCode::NOT_MODIFIED => {},
+                        }
                         Err(e) => yield Err(e),
                     }
                 }
+
+                is_baseline = false;
             }
         }
     }
@@ -318,13 +766,14 @@ impl ApolloConfClient {
         let executors = requests.into_iter().map(|fetch_request| async move {
             (
                 fetch_request.namespace_name(),
-                self.execute(fetch_request).await,
+                self.fetch(fetch_request).await,
             )
         });
 
         let executors_len = executors.len();
         let executors_stream = stream::iter(executors);
-        let mut buffered = executors_stream.buffer_unordered(executors_len);
+        // Zero limit never polls the executors, and never ends.
+        let mut buffered = executors_stream.buffer_unordered(executors_len.max(1));
 
         let mut map = HashMap::with_capacity(executors_len);
         while let Some(item) = buffered.next().await {
@@ -0,0 +1,258 @@
+//! Format-aware parsing of namespaces, dispatched on [NamespaceKind].
+
+use crate::{
+    conf::responses::FetchResponse,
+    errors::{ApolloClientError, ApolloClientResult},
+    meta::NamespaceKind,
+};
+#[cfg(feature = "xml")]
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Parsed configurations of a namespace.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedNamespace {
+    /// Key value pairs of `.properties` namespace.
+    Properties(HashMap<String, String>),
+    /// Content of `.json` namespace.
+    Json(Value),
+    /// Content of `.yaml` or `.yml` namespace, requires the feature `yaml`.
+    Yaml(Value),
+    /// Content of `.xml` namespace, converted to [Value], see [parse_namespace], requires the
+    /// feature `xml`.
+    Xml(Value),
+    /// Content of `.txt` namespace.
+    Txt(String),
+}
+
+impl ParsedNamespace {
+    /// Kind of the namespace.
+    pub fn kind(&self) -> NamespaceKind {
+        match self {
+            ParsedNamespace::Properties(_) => NamespaceKind::Properties,
+            ParsedNamespace::Json(_) => NamespaceKind::Json,
+            ParsedNamespace::Yaml(_) => NamespaceKind::Yaml,
+            ParsedNamespace::Xml(_) => NamespaceKind::Xml,
+            ParsedNamespace::Txt(_) => NamespaceKind::Txt,
+        }
+    }
+
+    /// The json value of json, yaml and xml namespace.
+    pub fn as_value(&self) -> Option<&Value> {
+        match self {
+            ParsedNamespace::Json(value)
+            | ParsedNamespace::Yaml(value)
+            | ParsedNamespace::Xml(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the configurations of namespace, the kind is inferred by the namespace name.
+///
+/// The yaml and xml namespaces require the feature `yaml` and `xml`, otherwise
+/// [ApolloClientError::FeatureRequired] is returned.
+///
+/// The xml content is converted to [Value] like:
+///
+/// - The root element is an object with the root tag name as the only key.
+/// - Attributes are the keys prefixed with `@`.
+/// - Child elements are the keys of tag names, the repeated ones are collected into array.
+/// - Text is the key `#text`, or the element itself if the element has only text.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "xml")]
+/// # {
+/// use apollo_client::conf::parse::{parse_namespace, ParsedNamespace};
+/// use serde_json::json;
+/// use std::collections::HashMap;
+///
+/// let configurations = HashMap::from([(
+///     "content".to_string(),
+///     r#"<db timeout="100"><host>a</host><host>b</host></db>"#.to_string(),
+/// )]);
+/// let parsed = parse_namespace("application.xml", &configurations).unwrap();
+/// assert_eq!(
+///     parsed,
+///     ParsedNamespace::Xml(json!({"db": {"@timeout": "100", "host": ["a", "b"]}}))
+/// );
+/// # }
+/// ```
+pub fn parse_namespace(
+    namespace_name: &str,
+    configurations: &HashMap<String, String>,
+) -> ApolloClientResult<ParsedNamespace> {
+    let kind = NamespaceKind::infer_namespace_kind(namespace_name);
+    if kind == NamespaceKind::Properties {
+        return Ok(ParsedNamespace::Properties(configurations.clone()));
+    }
+
+    let content = configurations
+        .get("content")
+        .ok_or_else(|| ApolloClientError::MissingContent(namespace_name.to_string()))?;
+
+    Ok(match kind {
+        NamespaceKind::Json => ParsedNamespace::Json(serde_json::from_str(content)?),
+        #[cfg(feature = "yaml")]
+        NamespaceKind::Yaml => ParsedNamespace::Yaml(serde_yaml::from_str(content)?),
+        #[cfg(feature = "xml")]
+        NamespaceKind::Xml => ParsedNamespace::Xml(parse_xml(content)?),
+        #[cfg(not(feature = "yaml"))]
+        NamespaceKind::Yaml => {
+            return Err(ApolloClientError::FeatureRequired(
+                namespace_name.to_string(),
+                "yaml",
+            ))
+        }
+        #[cfg(not(feature = "xml"))]
+        NamespaceKind::Xml => {
+            return Err(ApolloClientError::FeatureRequired(
+                namespace_name.to_string(),
+                "xml",
+            ))
+        }
+        _ => ParsedNamespace::Txt(content.clone()),
+    })
+}
+
+impl FetchResponse {
+    /// Parse the configurations, see [parse_namespace].
+    pub fn parsed(&self) -> ApolloClientResult<ParsedNamespace> {
+        parse_namespace(&self.namespace_name, &self.configurations)
+    }
+}
+
+#[cfg(feature = "xml")]
+fn parse_xml(content: &str) -> ApolloClientResult<Value> {
+    let document = roxmltree::Document::parse(content)?;
+    let root = document.root_element();
+    let mut map = Map::new();
+    map.insert(
+        root.tag_name().name().to_string(),
+        xml_element_to_value(root),
+    );
+    Ok(Value::Object(map))
+}
+
+#[cfg(feature = "xml")]
+fn xml_element_to_value(element: roxmltree::Node<'_, '_>) -> Value {
+    let mut map = Map::new();
+
+    for attribute in element.attributes() {
+        map.insert(
+            format!("@{}", attribute.name()),
+            Value::String(attribute.value().to_string()),
+        );
+    }
+
+    let mut text = String::new();
+    for child in element.children() {
+        if child.is_element() {
+            let name = child.tag_name().name().to_string();
+            let value = xml_element_to_value(child);
+            match map.get_mut(&name) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(existed) => {
+                    let existed = existed.take();
+                    map.insert(name, Value::Array(vec![existed, value]));
+                }
+                None => {
+                    map.insert(name, value);
+                }
+            }
+        } else if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+        }
+    }
+
+    let text = text.trim();
+    if map.is_empty() {
+        return Value::String(text.to_string());
+    }
+    if !text.is_empty() {
+        map.insert("#text".to_string(), Value::String(text.to_string()));
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn content(content: &str) -> HashMap<String, String> {
+        HashMap::from([("content".to_string(), content.to_string())])
+    }
+
+    #[test]
+    fn test_parse_namespace() {
+        let configurations = HashMap::from([("timeout".to_string(), "100".to_string())]);
+        assert_eq!(
+            parse_namespace("application", &configurations).unwrap(),
+            ParsedNamespace::Properties(configurations)
+        );
+
+        assert_eq!(
+            parse_namespace("application.json", &content(r#"{"timeout": 100}"#)).unwrap(),
+            ParsedNamespace::Json(json!({"timeout": 100}))
+        );
+
+        #[cfg(feature = "yaml")]
+        assert_eq!(
+            parse_namespace("application.yml", &content("timeout: 100\nhosts: [a, b]")).unwrap(),
+            ParsedNamespace::Yaml(json!({"timeout": 100, "hosts": ["a", "b"]}))
+        );
+
+        #[cfg(feature = "xml")]
+        assert_eq!(
+            parse_namespace(
+                "application.xml",
+                &content(r#"<a x="1"><b>text</b><c y="2">inner</c>tail</a>"#)
+            )
+            .unwrap(),
+            ParsedNamespace::Xml(json!({
+                "a": {"@x": "1", "b": "text", "c": {"@y": "2", "#text": "inner"}, "#text": "tail"}
+            }))
+        );
+
+        assert_eq!(
+            parse_namespace("application.txt", &content("plain text")).unwrap(),
+            ParsedNamespace::Txt("plain text".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_namespace_failed() {
+        assert!(matches!(
+            parse_namespace("application.json", &content("{")),
+            Err(ApolloClientError::SerdeJson(_))
+        ));
+        #[cfg(feature = "yaml")]
+        assert!(matches!(
+            parse_namespace("application.yaml", &content("a: [")),
+            Err(ApolloClientError::SerdeYaml(_))
+        ));
+        #[cfg(not(feature = "yaml"))]
+        assert!(matches!(
+            parse_namespace("application.yaml", &content("a: [")),
+            Err(ApolloClientError::FeatureRequired(_, "yaml"))
+        ));
+        #[cfg(feature = "xml")]
+        assert!(matches!(
+            parse_namespace("application.xml", &content("<a>")),
+            Err(ApolloClientError::XmlParse(_))
+        ));
+        #[cfg(not(feature = "xml"))]
+        assert!(matches!(
+            parse_namespace("application.xml", &content("<a>")),
+            Err(ApolloClientError::FeatureRequired(_, "xml"))
+        ));
+        assert!(matches!(
+            parse_namespace("application.txt", &HashMap::new()),
+            Err(ApolloClientError::MissingContent(_))
+        ));
+    }
+}
@@ -2,25 +2,39 @@
 
 use crate::{
     conf::{
-        meta::{IpValue, Notification},
-        responses::FetchResponse,
+        meta::{ApolloNotificationMessages, IpValue, Notification},
+        responses::{FetchResponse, ServiceResponse},
     },
     errors::ApolloClientResult,
     meta::{PerformRequest, DEFAULT_CLUSTER_NAME, DEFAULT_NOTIFY_TIMEOUT},
+    properties::Properties,
+    transport::{HttpRequest, Timeout},
 };
-use ini::Properties;
-use reqwest::RequestBuilder;
 use std::{borrow::Cow, time::Duration};
 
 /// Request executed by [crate::conf::ApolloConfClient::execute];
 pub(crate) trait PerformConfRequest: PerformRequest {}
 
+/// Discover config service instances from meta server.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConfigServicesRequest;
+
+impl PerformRequest for ConfigServicesRequest {
+    type Response = Vec<ServiceResponse>;
+
+    fn path(&self) -> String {
+        "/services/config".to_string()
+    }
+}
+
 /// Request configuration from cache.
 #[derive(Clone, Debug)]
 pub struct CachedFetchRequest {
     pub app_id: String,
     pub namespace_name: String,
     pub ip: Option<IpValue>,
+    /// Label of the instance, to match the rules of gray release.
+    pub label: Option<String>,
     pub cluster_name: String,
     pub extras_queries: Vec<(String, String)>,
     #[cfg(feature = "auth")]
@@ -34,6 +48,7 @@ impl Default for CachedFetchRequest {
             app_id: "".to_string(),
             namespace_name: "".to_string(),
             ip: None,
+            label: None,
             cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
             extras_queries: vec![],
             #[cfg(feature = "auth")]
@@ -59,6 +74,9 @@ impl PerformRequest for CachedFetchRequest {
         if let Some(ip) = &self.ip {
             pairs.push(("ip".into(), ip.to_string().into()));
         }
+        if let Some(label) = &self.label {
+            pairs.push(("label".into(), label.clone().into()));
+        }
         if !self.extras_queries.is_empty() {
             pairs.extend(
                 self.extras_queries
@@ -88,7 +106,11 @@ pub struct FetchRequest {
     pub namespace_name: String,
     pub cluster_name: String,
     pub ip: Option<IpValue>,
+    /// Label of the instance, to match the rules of gray release.
+    pub label: Option<String>,
     pub release_key: Option<String>,
+    /// Release messages of the notification, set by [crate::conf::ApolloConfClient::watch].
+    pub messages: Option<ApolloNotificationMessages>,
     pub extras_queries: Vec<(String, String)>,
     #[cfg(feature = "auth")]
     #[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
@@ -102,7 +124,9 @@ impl Default for FetchRequest {
             namespace_name: "".to_string(),
             cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
             ip: None,
+            label: None,
             release_key: None,
+            messages: None,
             extras_queries: vec![],
             #[cfg(feature = "auth")]
             access_key: None,
@@ -121,7 +145,9 @@ impl FetchRequest {
             cluster_name: watch.cluster_name.clone(),
             namespace_name,
             ip: watch.ip.clone(),
+            label: watch.label.clone(),
             release_key: None,
+            messages: None,
             extras_queries: watch.extras_queries.clone(),
             #[cfg(feature = "auth")]
             access_key: watch.access_key.clone(),
@@ -146,9 +172,15 @@ impl PerformRequest for FetchRequest {
         if let Some(ip) = &self.ip {
             pairs.push(("ip".into(), ip.to_string().into()));
         }
+        if let Some(label) = &self.label {
+            pairs.push(("label".into(), label.clone().into()));
+        }
         if let Some(release_key) = &self.release_key {
             pairs.push(("releaseKey".into(), release_key.clone().into()));
         }
+        if let Some(messages) = &self.messages {
+            pairs.push(("messages".into(), serde_json::to_string(messages)?.into()));
+        }
         if !self.extras_queries.is_empty() {
             pairs.extend(
                 self.extras_queries
@@ -232,15 +264,15 @@ impl PerformRequest for NotifyRequest {
         ])
     }
 
-    #[allow(unused_mut)]
-    fn request_builder(&self, mut request_builder: RequestBuilder) -> RequestBuilder {
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
         //FIXME
         //see issue #15701 <https://github.com/rust-lang/rust/issues/15701>
         #[cfg(feature = "auth")]
         {
-            request_builder = self.signature(request_builder);
+            self.signature(request)?;
         }
-        request_builder.timeout(self.timeout)
+        request.extensions_mut().insert(Timeout(self.timeout));
+        Ok(())
     }
 
     fn app_id(&self) -> Option<&str> {
@@ -264,6 +296,8 @@ pub struct WatchRequest {
     pub namespace_names: Vec<String>,
     pub cluster_name: String,
     pub ip: Option<IpValue>,
+    /// Label of the instance, to match the rules of gray release.
+    pub label: Option<String>,
     pub extras_queries: Vec<(String, String)>,
     #[cfg(feature = "auth")]
     pub access_key: Option<String>,
@@ -276,6 +310,7 @@ impl Default for WatchRequest {
             namespace_names: vec![],
             cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
             ip: None,
+            label: None,
             extras_queries: vec![],
             #[cfg(feature = "auth")]
             access_key: None,
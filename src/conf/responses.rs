@@ -12,6 +12,20 @@ pub struct FetchResponse {
     pub namespace_name: String,
     pub configurations: HashMap<String, String>,
     pub release_key: String,
+    /// Whether the response is loaded from the local cache, because apollo is unreachable.
+    #[serde(skip)]
+    pub from_cache: bool,
 }
 
 implement_json_perform_response_for! { FetchResponse }
+
+/// Response of config service instance discovered via meta server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceResponse {
+    pub app_name: String,
+    pub instance_id: String,
+    pub homepage_url: String,
+}
+
+implement_json_perform_response_for! { Vec<ServiceResponse> }
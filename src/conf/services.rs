@@ -0,0 +1,136 @@
+//! Config service instances, used for load balancing and failover.
+
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// How long a config service instance is skipped after failed.
+pub(crate) const UNHEALTHY_DURATION: Duration = Duration::from_secs(30);
+
+struct ServiceNode {
+    url: Url,
+    unhealthy_until: Option<Instant>,
+}
+
+impl ServiceNode {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            unhealthy_until: None,
+        }
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until
+            .map(|until| until <= now)
+            .unwrap_or(true)
+    }
+}
+
+/// The known config service instances, configured directly or discovered via meta server.
+#[derive(Default)]
+pub(crate) struct ConfigServices {
+    nodes: Vec<ServiceNode>,
+    discovered_at: Option<Instant>,
+    index: usize,
+}
+
+impl ConfigServices {
+    pub(crate) fn new(urls: Vec<Url>) -> Self {
+        Self {
+            nodes: urls.into_iter().map(ServiceNode::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub(crate) fn is_discovery_expired(&self, interval: Duration) -> bool {
+        self.discovered_at
+            .map(|at| at.elapsed() >= interval)
+            .unwrap_or(true)
+    }
+
+    /// Replace the instances with the discovered ones, the health of remained instances is kept.
+    pub(crate) fn update_discovered(&mut self, urls: Vec<Url>) {
+        let mut older = std::mem::take(&mut self.nodes);
+        self.nodes = urls
+            .into_iter()
+            .map(|url| match older.iter().position(|node| node.url == url) {
+                Some(position) => older.swap_remove(position),
+                None => ServiceNode::new(url),
+            })
+            .collect();
+        self.discovered_at = Some(Instant::now());
+    }
+
+    /// The instances to try in order, start from the next one in turn, and the unhealthy ones
+    /// are put at the end as the last resort.
+    pub(crate) fn candidates(&mut self) -> Vec<Url> {
+        if self.nodes.is_empty() {
+            return vec![];
+        }
+
+        let len = self.nodes.len();
+        self.index = (self.index + 1) % len;
+        let now = Instant::now();
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = (0..len)
+            .map(|offset| &self.nodes[(self.index + offset) % len])
+            .partition(|node| node.is_healthy(now));
+        healthy
+            .into_iter()
+            .chain(unhealthy)
+            .map(|node| node.url.clone())
+            .collect()
+    }
+
+    pub(crate) fn mark_unhealthy(&mut self, url: &Url) {
+        if let Some(node) = self.nodes.iter_mut().find(|node| &node.url == url) {
+            node.unhealthy_until = Some(Instant::now() + UNHEALTHY_DURATION);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(ports: &[u16]) -> Vec<Url> {
+        ports
+            .iter()
+            .map(|port| Url::parse(&format!("http://localhost:{}", port)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_round_robin() {
+        let mut services = ConfigServices::new(urls(&[8080, 8081, 8082]));
+        assert_eq!(services.candidates(), urls(&[8081, 8082, 8080]));
+        assert_eq!(services.candidates(), urls(&[8082, 8080, 8081]));
+        assert_eq!(services.candidates(), urls(&[8080, 8081, 8082]));
+        assert_eq!(ConfigServices::default().candidates(), vec![]);
+    }
+
+    #[test]
+    fn test_candidates_unhealthy_last() {
+        let mut services = ConfigServices::new(urls(&[8080, 8081, 8082]));
+        services.mark_unhealthy(&urls(&[8081])[0]);
+        assert_eq!(services.candidates(), urls(&[8082, 8080, 8081]));
+        assert_eq!(services.candidates(), urls(&[8082, 8080, 8081]));
+        assert_eq!(services.candidates(), urls(&[8080, 8082, 8081]));
+    }
+
+    #[test]
+    fn test_update_discovered() {
+        let mut services = ConfigServices::new(urls(&[8080, 8081]));
+        assert!(services.is_discovery_expired(Duration::from_secs(60)));
+
+        services.mark_unhealthy(&urls(&[8080])[0]);
+        services.update_discovered(urls(&[8080, 8082]));
+        assert!(!services.is_discovery_expired(Duration::from_secs(60)));
+        assert_eq!(services.candidates(), urls(&[8082, 8080]));
+        assert_eq!(services.candidates(), urls(&[8082, 8080]));
+    }
+}
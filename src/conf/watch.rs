@@ -0,0 +1,281 @@
+//! Handle to control the watching created by [crate::conf::ApolloConfClient::watch_with_handle].
+
+use crate::{
+    conf::meta::{ApolloNotificationMessages, Notification},
+    utils::canonicalize_namespace,
+};
+use futures_util::stream::AbortHandle;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::Notify;
+
+/// Handle of a watch stream, can be cloned and used in other tasks than the one polling the
+/// stream.
+#[derive(Debug, Clone)]
+pub struct WatchHandle {
+    abort_handle: AbortHandle,
+    shared: Arc<WatchShared>,
+}
+
+impl WatchHandle {
+    pub(crate) fn new(abort_handle: AbortHandle, shared: Arc<WatchShared>) -> Self {
+        Self {
+            abort_handle,
+            shared,
+        }
+    }
+
+    /// Stop the watching, the stream ends immediately, without waiting for the in-flight long
+    /// poll, which is cancelled when the stream is dropped.
+    pub fn stop(&self) {
+        self.abort_handle.abort();
+    }
+
+    /// Whether [WatchHandle::stop] is called.
+    pub fn is_stopped(&self) -> bool {
+        self.abort_handle.is_aborted()
+    }
+
+    /// Add the namespace to the running watching, the namespace is fetched and yielded by the
+    /// stream soon, the other namespaces are not re-fetched.
+    ///
+    /// Return `false` if the namespace is already watched.
+    pub fn subscribe(&self, namespace_name: impl Into<String>) -> bool {
+        let subscribed = self.shared.subscribe(namespace_name.into());
+        if subscribed {
+            self.shared.changed.notify_one();
+        }
+        subscribed
+    }
+
+    /// Remove the namespace from the running watching, the watching idles if no namespaces
+    /// remained, until new namespace subscribed.
+    ///
+    /// Return `false` if the namespace is not watched.
+    pub fn unsubscribe(&self, namespace_name: &str) -> bool {
+        let unsubscribed = self.shared.unsubscribe(namespace_name);
+        if unsubscribed {
+            self.shared.changed.notify_one();
+        }
+        unsubscribed
+    }
+
+    /// Names of the watched namespaces.
+    pub fn namespace_names(&self) -> Vec<String> {
+        self.shared
+            .state
+            .lock()
+            .unwrap()
+            .notifications
+            .iter()
+            .map(|notification| notification.namespace_name.clone())
+            .collect()
+    }
+}
+
+/// State shared between the watch stream and the [WatchHandle].
+#[derive(Debug)]
+pub(crate) struct WatchShared {
+    state: Mutex<WatchState>,
+    /// Notified when the watched namespaces changed, to interrupt the in-flight long poll.
+    pub(crate) changed: Notify,
+}
+
+#[derive(Debug)]
+struct WatchState {
+    /// Notifications sent by the long poll.
+    notifications: Vec<Notification>,
+    /// Subscribed namespaces not fetched yet.
+    pending: Vec<Notification>,
+    /// Merged release messages of the canonical namespaces.
+    messages: HashMap<String, ApolloNotificationMessages>,
+}
+
+impl WatchShared {
+    pub(crate) fn new(notifications: Vec<Notification>) -> Self {
+        let shared = Self {
+            state: Mutex::new(WatchState {
+                notifications: vec![],
+                pending: vec![],
+                messages: HashMap::new(),
+            }),
+            changed: Notify::new(),
+        };
+        for notification in notifications {
+            shared.subscribe(notification.namespace_name);
+        }
+        shared
+    }
+
+    fn subscribe(&self, namespace_name: String) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let canonical_name = canonicalize_namespace(&namespace_name);
+        if state.notifications.iter().any(|notification| {
+            canonicalize_namespace(&notification.namespace_name) == canonical_name
+        }) {
+            return false;
+        }
+
+        let notification = Notification {
+            namespace_name,
+            ..Default::default()
+        };
+        state.notifications.push(notification.clone());
+        state.pending.push(notification);
+        true
+    }
+
+    fn unsubscribe(&self, namespace_name: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let canonical_name = canonicalize_namespace(namespace_name);
+        let is_other = |notification: &Notification| {
+            canonicalize_namespace(&notification.namespace_name) != canonical_name
+        };
+
+        let len = state.notifications.len();
+        state.notifications.retain(is_other);
+        state.pending.retain(is_other);
+        state.messages.remove(&canonical_name);
+        state.notifications.len() != len
+    }
+
+    /// Notifications to send by the long poll.
+    pub(crate) fn notifications(&self) -> Vec<Notification> {
+        self.state.lock().unwrap().notifications.clone()
+    }
+
+    /// Take the subscribed namespaces which should be fetched.
+    pub(crate) fn take_pending(&self) -> Vec<Notification> {
+        std::mem::take(&mut self.state.lock().unwrap().pending)
+    }
+
+    /// Update the notification ids by the long poll response, return the namespaces which
+    /// should be fetched.
+    ///
+    /// The uninitialized namespaces are just fetched when subscribed, so only the ids are
+    /// updated. The release messages are merged with the previous ones of the namespace.
+    pub(crate) fn update(&self, newer: &[Notification]) -> Vec<Notification> {
+        let mut state = self.state.lock().unwrap();
+        let mut changed = Vec::new();
+
+        for newer_item in newer {
+            let newer_namespace_name = canonicalize_namespace(&newer_item.namespace_name);
+            let older_item = state.notifications.iter().find(|older_item| {
+                canonicalize_namespace(&older_item.namespace_name) == newer_namespace_name
+            });
+            let is_changed = match older_item {
+                Some(older_item) => !older_item.is_uninitialized(),
+                // Unsubscribed.
+                None => continue,
+            };
+
+            let mut newer_item = newer_item.clone();
+            if let Some(newer_messages) = &newer_item.messages {
+                let messages = state.messages.entry(newer_namespace_name).or_default();
+                messages.merge_from(newer_messages);
+                newer_item.messages = Some(messages.clone());
+            }
+            if is_changed {
+                changed.push(newer_item);
+            }
+        }
+
+        Notification::update_notifications(&mut state.notifications, newer);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(namespace_name: &str, notification_id: i32) -> Notification {
+        Notification {
+            namespace_name: namespace_name.to_string(),
+            notification_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_subscribe_and_unsubscribe() {
+        let shared = WatchShared::new(vec![notification("foo", -1)]);
+        assert_eq!(shared.take_pending(), [notification("foo", -1)]);
+        assert!(shared.take_pending().is_empty());
+
+        assert!(!shared.subscribe("foo.properties".to_string()));
+        assert!(shared.subscribe("bar.json".to_string()));
+        assert!(shared.subscribe("baz".to_string()));
+        assert!(shared.unsubscribe("baz.properties"));
+        assert!(!shared.unsubscribe("baz"));
+
+        assert_eq!(
+            shared.notifications(),
+            [notification("foo", -1), notification("bar.json", -1)]
+        );
+        assert_eq!(shared.take_pending(), [notification("bar.json", -1)]);
+
+        assert!(shared.unsubscribe("foo"));
+        assert!(shared.unsubscribe("bar.json"));
+        assert!(shared.notifications().is_empty());
+    }
+
+    #[test]
+    fn test_update() {
+        let shared = WatchShared::new(vec![notification("foo", -1), notification("bar", -1)]);
+
+        // The first response only initialize the ids.
+        assert!(shared
+            .update(&[notification("foo", 1), notification("bar", 2)])
+            .is_empty());
+
+        shared.subscribe("baz".to_string());
+        assert_eq!(
+            shared.update(&[notification("foo", 3), notification("baz", 4)]),
+            [notification("foo", 3)]
+        );
+        assert_eq!(
+            shared.notifications(),
+            [
+                notification("foo", 3),
+                notification("bar", 2),
+                notification("baz", 4)
+            ]
+        );
+
+        // Unsubscribed namespaces are ignored.
+        assert!(shared.update(&[notification("qux", 5)]).is_empty());
+    }
+
+    #[test]
+    fn test_update_messages() {
+        let shared = WatchShared::new(vec![notification("foo", 1)]);
+        shared.update(&[notification("foo", 1)]);
+
+        let with_messages = |notification_id, details: &[(&str, i64)]| Notification {
+            messages: Some(ApolloNotificationMessages {
+                details: details
+                    .iter()
+                    .map(|(key, id)| (key.to_string(), *id))
+                    .collect(),
+            }),
+            ..notification("foo", notification_id)
+        };
+
+        assert_eq!(
+            shared.update(&[with_messages(2, &[("App+default+foo", 2)])]),
+            [with_messages(2, &[("App+default+foo", 2)])]
+        );
+        assert_eq!(
+            shared.update(&[with_messages(3, &[("App+gray+foo", 3)])]),
+            [with_messages(
+                3,
+                &[("App+default+foo", 2), ("App+gray+foo", 3)]
+            )]
+        );
+        // Messages are not sent by the long poll.
+        assert_eq!(shared.notifications(), [notification("foo", 3)]);
+    }
+}
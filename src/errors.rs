@@ -1,7 +1,7 @@
 //! Crate level errors.
 
+use crate::transport::HttpResponse;
 use http::StatusCode;
-use reqwest::Response;
 use std::str::Utf8Error;
 
 /// Crate level result.
@@ -10,28 +10,85 @@ pub type ApolloClientResult<T> = Result<T, ApolloClientError>;
 /// Crate level error.
 #[derive(thiserror::Error, Debug)]
 pub enum ApolloClientError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     #[error(transparent)]
     Utf8(#[from] Utf8Error),
 
     #[error(transparent)]
     Http(#[from] http::Error),
 
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
 
+    /// Error of custom [crate::transport::HttpTransport].
+    #[error(transparent)]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 
+    #[error(transparent)]
+    PropertiesParse(#[from] crate::properties::ParseError),
+
+    #[cfg(feature = "yaml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    #[error(transparent)]
+    SerdeYaml(#[from] serde_yaml::Error),
+
+    #[cfg(feature = "xml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+    #[error(transparent)]
+    XmlParse(#[from] roxmltree::Error),
+
     #[cfg(feature = "conf")]
     #[cfg_attr(docsrs, doc(cfg(feature = "conf")))]
     #[error(transparent)]
-    IniParse(#[from] ini::ParseError),
+    Deserialize(#[from] crate::conf::de::DeError),
 
     #[error(transparent)]
     ApolloResponse(#[from] ApolloResponseError),
 
+    #[error(transparent)]
+    UrlParse(#[from] url::ParseError),
+
     #[error("this URL is cannot-be-a-base")]
     UrlCannotBeABase,
+
+    #[error("no config service instance is available")]
+    NoConfigService,
+
+    #[error("the `content` of namespace `{0}` is missing")]
+    MissingContent(String),
+
+    #[error("the feature `{1}` is required to parse namespace `{0}`")]
+    FeatureRequired(String, &'static str),
+
+    #[error("no http transport is specified")]
+    NoTransport,
+
+    #[error("the key `{0}` is duplicated")]
+    DuplicateKey(String),
+}
+
+impl ApolloClientError {
+    /// Whether the error is caused by the server being unreachable or failed, such as connection
+    /// errors, timeouts, errors of sending the request or reading the response body, and 5xx
+    /// responses.
+    pub fn is_server_unavailable(&self) -> bool {
+        match self {
+            #[cfg(feature = "reqwest")]
+            ApolloClientError::Reqwest(e) => {
+                e.is_connect() || e.is_timeout() || e.is_request() || e.is_body()
+            }
+            ApolloClientError::Transport(_) => true,
+            ApolloClientError::ApolloResponse(e) => e.status.is_server_error(),
+            _ => false,
+        }
+    }
 }
 
 /// Apollo api response error, when http status is not success.
@@ -45,13 +102,13 @@ pub struct ApolloResponseError {
 }
 
 impl ApolloResponseError {
-    pub(crate) async fn from_response(response: Response) -> Result<Response, Self> {
+    pub(crate) fn from_response(response: HttpResponse) -> Result<HttpResponse, Self> {
         if response.status().is_success() {
             Ok(response)
         } else {
             Err(Self {
                 status: response.status(),
-                body: response.text().await.unwrap_or_default(),
+                body: String::from_utf8_lossy(response.body()).into_owned(),
             })
         }
     }
@@ -1,7 +1,9 @@
 //! Common api metadata.
 
-use crate::errors::{ApolloClientResult, ApolloResponseError};
-use reqwest::Response;
+use crate::{
+    errors::{ApolloClientResult, ApolloResponseError},
+    transport::{HttpRequest, HttpResponse},
+};
 use std::{fmt, fmt::Display, time::Duration};
 
 #[allow(dead_code)]
@@ -54,7 +56,7 @@ impl Display for NamespaceKind {
 }
 
 /// Common api request trait.
-#[cfg(feature = "conf")]
+#[cfg(any(feature = "conf", feature = "open"))]
 pub(crate) trait PerformRequest {
     /// The returned response after request is success.
     type Response: PerformResponse;
@@ -74,19 +76,16 @@ pub(crate) trait PerformRequest {
         Ok(vec![])
     }
 
-    /// Handle extras operator, such as set request body.
-    #[allow(unused_mut)]
-    fn request_builder(
-        &self,
-        mut request_builder: reqwest::RequestBuilder,
-    ) -> reqwest::RequestBuilder {
+    /// Handle extras operator, such as set request headers and body.
+    #[allow(unused_variables)]
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
         //FIXME
         //see issue #15701 <https://github.com/rust-lang/rust/issues/15701>
         #[cfg(all(feature = "auth", feature = "conf"))]
         {
-            request_builder = self.signature(request_builder);
+            self.signature(request)?;
         }
-        request_builder
+        Ok(())
     }
 
     /// AppId
@@ -107,7 +106,7 @@ pub(crate) trait PerformRequest {
     ///
     /// https://www.apolloconfig.com/#/zh/usage/other-language-client-user-guide?id=_15-%e9%85%8d%e7%bd%ae%e8%ae%bf%e9%97%ae%e5%af%86%e9%92%a5
     #[cfg(all(feature = "auth", feature = "conf"))]
-    fn signature(&self, mut request_builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    fn signature(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
         use base64::{engine::general_purpose::STANDARD, Engine};
         use hmac::{Mac, SimpleHmac};
         use sha1::Sha1;
@@ -131,46 +130,54 @@ pub(crate) trait PerformRequest {
             if let Ok(mut hmac) = HmacWithSha1::new_from_slice(access_key.as_bytes()) {
                 hmac.update(format!("{}\n{}", ts, url).as_bytes());
                 let sign = STANDARD.encode(hmac.finalize().into_bytes());
-                request_builder = request_builder
-                    .header(
-                        reqwest::header::AUTHORIZATION,
-                        format!("Apollo {}:{}", app_id, sign),
-                    )
-                    .header("Timestamp", ts);
+                let headers = request.headers_mut();
+                headers.insert(
+                    http::header::AUTHORIZATION,
+                    http::HeaderValue::try_from(format!("Apollo {}:{}", app_id, sign))
+                        .map_err(http::Error::from)?,
+                );
+                headers.insert("Timestamp", ts.into());
             }
         }
-        request_builder
+        Ok(())
     }
 }
 
 /// Common api response trait.
-#[cfg(feature = "conf")]
-#[async_trait::async_trait]
+#[cfg(any(feature = "conf", feature = "open"))]
 pub(crate) trait PerformResponse: Sized {
     /// Create Self from response.
-    async fn from_response(response: Response) -> ApolloClientResult<Self>;
+    fn from_response(response: HttpResponse) -> ApolloClientResult<Self>;
 }
 
-#[cfg(feature = "conf")]
-#[async_trait::async_trait]
+#[cfg(any(feature = "conf", feature = "open"))]
 impl PerformResponse for () {
-    async fn from_response(_response: Response) -> ApolloClientResult<Self> {
+    fn from_response(_response: HttpResponse) -> ApolloClientResult<Self> {
         Ok(())
     }
 }
 
+/// Empty body is parsed as `None`, such as the apollo responding null.
+#[cfg(any(feature = "conf", feature = "open"))]
+impl<T: serde::de::DeserializeOwned> PerformResponse for Option<T> {
+    fn from_response(response: HttpResponse) -> ApolloClientResult<Self> {
+        if response.body().iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+        Ok(serde_json::from_slice(response.body())?)
+    }
+}
+
 #[cfg(feature = "conf")]
-#[async_trait::async_trait]
-impl PerformResponse for ini::Properties {
-    async fn from_response(response: Response) -> ApolloClientResult<Self> {
-        let content = response.text().await?;
-        let i = ini::Ini::load_from_str(&content)?;
-        Ok(i.section(None::<&'static str>).cloned().unwrap_or_default())
+impl PerformResponse for crate::properties::Properties {
+    fn from_response(response: HttpResponse) -> ApolloClientResult<Self> {
+        let content = std::str::from_utf8(response.body())?;
+        Ok(content.parse()?)
     }
 }
 
 /// Create request url from base url, mainly path and queries.
-#[cfg(feature = "conf")]
+#[cfg(any(feature = "conf", feature = "open"))]
 pub(crate) fn handle_url(
     request: &impl PerformRequest,
     base_url: url::Url,
@@ -189,24 +196,50 @@ pub(crate) fn handle_url(
     Ok(url)
 }
 
+/// Create http request from base url, with the headers and body set by the request.
+#[cfg(any(feature = "conf", feature = "open"))]
+pub(crate) fn build_request(
+    request: &impl PerformRequest,
+    base_url: url::Url,
+) -> ApolloClientResult<HttpRequest> {
+    let url = handle_url(request, base_url)?;
+    let mut http_request = http::Request::builder()
+        .method(request.method())
+        .uri(url.as_str())
+        .body(Vec::new())?;
+    request.handle_request(&mut http_request)?;
+    Ok(http_request)
+}
+
+/// Set the json body and content type of request.
+#[allow(dead_code)]
+pub(crate) fn set_json_body(
+    request: &mut HttpRequest,
+    body: &impl serde::Serialize,
+) -> ApolloClientResult<()> {
+    *request.body_mut() = serde_json::to_vec(body)?;
+    request.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    Ok(())
+}
+
 /// Validate response is successful or not.
 #[allow(dead_code)]
-pub(crate) async fn validate_response(response: Response) -> ApolloClientResult<Response> {
-    ApolloResponseError::from_response(response)
-        .await
-        .map_err(Into::into)
+pub(crate) fn validate_response(response: HttpResponse) -> ApolloClientResult<HttpResponse> {
+    ApolloResponseError::from_response(response).map_err(Into::into)
 }
 
 /// Implement PerformResponse for response struct which content type is `application/json`.
 #[allow(unused_macros)]
 macro_rules! implement_json_perform_response_for {
     ($t:ty) => {
-        #[async_trait::async_trait]
         impl $crate::meta::PerformResponse for $t {
-            async fn from_response(
-                response: ::reqwest::Response,
+            fn from_response(
+                response: $crate::transport::HttpResponse,
             ) -> $crate::errors::ApolloClientResult<Self> {
-                Ok(response.json().await?)
+                Ok(::serde_json::from_slice(response.body())?)
             }
         }
     };
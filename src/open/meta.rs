@@ -1,6 +1,7 @@
 //! open api metadata.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Item for [crate::open::requests::OpenCreateItemRequest].
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,3 +65,107 @@ impl Default for OpenRelease {
         }
     }
 }
+
+/// Rule item of the gray release, the clients matching any of the ips or labels of the app get
+/// the gray configurations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenGrayReleaseRuleItem {
+    pub client_app_id: String,
+    #[serde(default)]
+    pub client_ip_list: Vec<String>,
+    #[serde(default)]
+    pub client_label_list: Vec<String>,
+}
+
+/// App for [crate::open::requests::OpenCreateAppRequest].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCreatedApp {
+    pub name: String,
+    pub app_id: String,
+    pub org_id: String,
+    pub org_name: String,
+    pub owner_name: String,
+    pub owner_email: String,
+}
+
+/// The differences of the configurations between two releases, sorted by key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseDiff {
+    /// The keys only in the new configurations, with the new values.
+    pub added: BTreeMap<String, String>,
+    /// The keys with different values, with the old and new values.
+    pub changed: BTreeMap<String, (String, String)>,
+    /// The keys only in the old configurations, with the old values.
+    pub removed: BTreeMap<String, String>,
+}
+
+impl ReleaseDiff {
+    /// Compare the old configurations to the new configurations.
+    pub fn new(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Self {
+        let mut diff = ReleaseDiff::default();
+        for (key, new_value) in new {
+            match old.get(key) {
+                None => {
+                    diff.added.insert(key.clone(), new_value.clone());
+                }
+                Some(old_value) if old_value != new_value => {
+                    diff.changed
+                        .insert(key.clone(), (old_value.clone(), new_value.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, old_value) in old {
+            if !new.contains_key(key) {
+                diff.removed.insert(key.clone(), old_value.clone());
+            }
+        }
+        diff
+    }
+
+    /// Whether the configurations are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configurations(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_release_diff() {
+        let old = configurations(&[("timeout", "100"), ("batch", "200"), ("host", "a")]);
+        let new = configurations(&[("timeout", "3000"), ("batch", "200"), ("port", "80")]);
+
+        let diff = ReleaseDiff::new(&old, &new);
+        assert_eq!(
+            diff.added,
+            BTreeMap::from([("port".to_string(), "80".to_string())])
+        );
+        assert_eq!(
+            diff.changed,
+            BTreeMap::from([(
+                "timeout".to_string(),
+                ("100".to_string(), "3000".to_string())
+            )])
+        );
+        assert_eq!(
+            diff.removed,
+            BTreeMap::from([("host".to_string(), "a".to_string())])
+        );
+        assert!(!diff.is_empty());
+
+        assert!(ReleaseDiff::new(&old, &old).is_empty());
+        assert_eq!(ReleaseDiff::new(&new, &old).added, diff.removed);
+    }
+}
@@ -33,52 +33,64 @@
 //! ```
 
 pub mod meta;
+pub mod promote;
 pub mod requests;
 pub mod responses;
+pub mod snapshot;
+pub mod sync;
 
 use crate::{
     errors::ApolloClientResult,
-    meta::{handle_url, validate_response, PerformResponse, DEFAULT_TIMEOUT},
+    meta::{build_request, validate_response, PerformResponse},
     open::{
         requests::{
-            OpenAppRequest, OpenClusterRequest, OpenCreateItemRequest, OpenEnvClusterRequest,
-            OpenNamespaceRequest, OpenPublishNamespaceRequest, OpenUpdateItemRequest,
-            PerformOpenRequest,
+            OpenAppRequest, OpenClusterRequest, OpenCreateAppRequest, OpenCreateBranchRequest,
+            OpenCreateClusterRequest, OpenCreateItemRequest, OpenCreateNamespaceRequest,
+            OpenDeleteBranchRequest, OpenDeleteItemRequest, OpenEnvClusterRequest,
+            OpenGetBranchRequest, OpenGetGrayRulesRequest, OpenGetItemRequest,
+            OpenGetNamespaceRequest, OpenGetReleaseRequest, OpenGrayReleaseRequest,
+            OpenLatestReleaseRequest, OpenLinkNamespaceRequest, OpenListItemsRequest,
+            OpenMergeBranchRequest, OpenNamespaceRequest, OpenPublishNamespaceRequest,
+            OpenReleaseHistoryRequest, OpenRollbackReleaseRequest, OpenUpdateGrayRulesRequest,
+            OpenUpdateItemRequest, PerformOpenRequest,
         },
         responses::{
-            OpenAppResponse, OpenClusterResponse, OpenEnvClusterResponse, OpenItemResponse,
-            OpenNamespaceResponse, OpenPublishResponse,
+            OpenAppNamespaceResponse, OpenAppResponse, OpenClusterResponse, OpenEnvClusterResponse,
+            OpenGrayReleaseRuleResponse, OpenItemResponse, OpenNamespaceResponse, OpenPageResponse,
+            OpenPublishResponse,
         },
     },
+    transport::HttpTransport,
 };
-use http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
-use reqwest::{Client, ClientBuilder};
+use http::{header::AUTHORIZATION, HeaderValue};
+#[cfg(feature = "reqwest")]
+use reqwest::ClientBuilder;
+use std::sync::Arc;
 use url::Url;
 
 /// The builder for [OpenApiClient].
 pub struct OpenApiClientBuilder {
     portal_url: Url,
-    token: String,
+    token: HeaderValue,
+    #[cfg(feature = "reqwest")]
     client_builder: ClientBuilder,
+    transport: Option<Arc<dyn HttpTransport>>,
 }
 
 impl OpenApiClientBuilder {
     /// Create a builder.
     pub fn new(portal_url: Url, token: impl ToString) -> ApolloClientResult<Self> {
-        let mut builder = Self {
+        Ok(Self {
             portal_url,
-            token: token.to_string(),
-            client_builder: Default::default(),
-        };
-        let default_headers = builder.default_headers()?;
-        builder.client_builder = builder
-            .client_builder
-            .timeout(DEFAULT_TIMEOUT)
-            .default_headers(default_headers);
-        Ok(builder)
+            token: HeaderValue::from_str(&token.to_string()).map_err(http::Error::from)?,
+            #[cfg(feature = "reqwest")]
+            client_builder: ClientBuilder::default().timeout(crate::meta::DEFAULT_TIMEOUT),
+            transport: None,
+        })
     }
 
-    /// Customize inner http client.
+    /// Customize inner http client, take no effect if [OpenApiClientBuilder::with_transport] is
+    /// called.
     ///
     /// # Example
     ///
@@ -93,30 +105,44 @@ impl OpenApiClientBuilder {
     /// .unwrap()
     /// .with_client_builder(|builder| builder.timeout(Duration::from_secs(6)));
     /// ```
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
     pub fn with_client_builder(mut self, f: impl FnOnce(ClientBuilder) -> ClientBuilder) -> Self {
         self.client_builder = f(self.client_builder);
         self
     }
 
+    /// Send the requests by the custom transport instead of the default reqwest client, see
+    /// [crate::transport].
+    pub fn with_transport(mut self, transport: impl HttpTransport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
     /// Build the [OpenApiClient].
     pub fn build(self) -> ApolloClientResult<OpenApiClient> {
+        let transport: Arc<dyn HttpTransport> = match self.transport {
+            Some(transport) => transport,
+            #[cfg(feature = "reqwest")]
+            None => Arc::new(crate::transport::ReqwestTransport::new(
+                self.client_builder.build()?,
+            )),
+            #[cfg(not(feature = "reqwest"))]
+            None => return Err(crate::errors::ApolloClientError::NoTransport),
+        };
         Ok(OpenApiClient {
             portal_url: self.portal_url,
-            client: self.client_builder.build()?,
+            token: self.token,
+            transport,
         })
     }
-
-    fn default_headers(&self) -> Result<HeaderMap, http::Error> {
-        let mut map = HeaderMap::new();
-        map.insert(AUTHORIZATION, HeaderValue::from_str(&self.token)?);
-        Ok(map)
-    }
 }
 
 /// Created by [OpenApiClientBuilder::build].
 pub struct OpenApiClient {
     portal_url: Url,
-    client: Client,
+    token: HeaderValue,
+    transport: Arc<dyn HttpTransport>,
 }
 
 impl OpenApiClient {
@@ -147,9 +173,9 @@ impl OpenApiClient {
         self.execute(request).await
     }
 
-    /// 获取某个Namespace信息接口。
+    /// 获取集群下所有Namespace信息接口。
     ///
-    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_326-%e8%8e%b7%e5%8f%96%e6%9f%90%e4%b8%aanamespace%e4%bf%a1%e6%81%af%e6%8e%a5%e5%8f%a3)
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_325-%e8%8e%b7%e5%8f%96%e9%9b%86%e7%be%a4%e4%b8%8b%e6%89%80%e6%9c%89namespace%e4%bf%a1%e6%81%af%e6%8e%a5%e5%8f%a3)
     pub async fn namespace(
         &self,
         request: OpenNamespaceRequest,
@@ -157,6 +183,26 @@ impl OpenApiClient {
         self.execute(request).await
     }
 
+    /// 获取某个Namespace信息接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_326-%e8%8e%b7%e5%8f%96%e6%9f%90%e4%b8%aanamespace%e4%bf%a1%e6%81%af%e6%8e%a5%e5%8f%a3)
+    pub async fn get_namespace(
+        &self,
+        request: OpenGetNamespaceRequest,
+    ) -> ApolloClientResult<OpenNamespaceResponse> {
+        self.execute(request).await
+    }
+
+    /// 读取配置接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_329-%e8%af%bb%e5%8f%96%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
+    pub async fn get_item(
+        &self,
+        request: OpenGetItemRequest,
+    ) -> ApolloClientResult<OpenItemResponse> {
+        self.execute(request).await
+    }
+
     /// 新增配置接口。
     ///
     /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3210-%e6%96%b0%e5%a2%9e%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
@@ -174,6 +220,23 @@ impl OpenApiClient {
         self.execute(request).await
     }
 
+    /// 删除配置接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3212-%e5%88%a0%e9%99%a4%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
+    pub async fn delete_item(&self, request: OpenDeleteItemRequest) -> ApolloClientResult<()> {
+        self.execute(request).await
+    }
+
+    /// 分页获取配置项接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3216-%e5%88%86%e9%a1%b5%e8%8e%b7%e5%8f%96%e9%85%8d%e7%bd%ae%e9%a1%b9%e6%8e%a5%e5%8f%a3)
+    pub async fn list_items(
+        &self,
+        request: OpenListItemsRequest,
+    ) -> ApolloClientResult<OpenPageResponse<OpenItemResponse>> {
+        self.execute(request).await
+    }
+
     /// 发布配置接口。
     ///
     /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3213-%e5%8f%91%e5%b8%83%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
@@ -184,15 +247,138 @@ impl OpenApiClient {
         self.execute(request).await
     }
 
+    /// 获取某个Namespace当前生效的已发布配置接口，从未发布过则返回`None`。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3214-%e8%8e%b7%e5%8f%96%e6%9f%90%e4%b8%aanamespace%e5%bd%93%e5%89%8d%e7%94%9f%e6%95%88%e7%9a%84%e5%b7%b2%e5%8f%91%e5%b8%83%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
+    pub async fn latest_release(
+        &self,
+        request: OpenLatestReleaseRequest,
+    ) -> ApolloClientResult<Option<OpenPublishResponse>> {
+        self.execute(request).await
+    }
+
+    /// 分页获取Namespace的发布历史，最新发布在前，包含已回滚的发布。
+    pub async fn release_history(
+        &self,
+        request: OpenReleaseHistoryRequest,
+    ) -> ApolloClientResult<OpenPageResponse<OpenPublishResponse>> {
+        self.execute(request).await
+    }
+
+    /// 获取某次发布。
+    pub async fn get_release(
+        &self,
+        request: OpenGetReleaseRequest,
+    ) -> ApolloClientResult<OpenPublishResponse> {
+        self.execute(request).await
+    }
+
+    /// 回滚已发布配置接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_3215-%e5%9b%9e%e6%bb%9a%e5%b7%b2%e5%8f%91%e5%b8%83%e9%85%8d%e7%bd%ae%e6%8e%a5%e5%8f%a3)
+    pub async fn rollback_release(
+        &self,
+        request: OpenRollbackReleaseRequest,
+    ) -> ApolloClientResult<()> {
+        self.execute(request).await
+    }
+
+    /// 创建灰度分支。
+    pub async fn create_branch(
+        &self,
+        request: OpenCreateBranchRequest,
+    ) -> ApolloClientResult<OpenNamespaceResponse> {
+        self.execute(request).await
+    }
+
+    /// 获取灰度分支，没有灰度分支则返回`None`。
+    pub async fn branch(
+        &self,
+        request: OpenGetBranchRequest,
+    ) -> ApolloClientResult<Option<OpenNamespaceResponse>> {
+        self.execute(request).await
+    }
+
+    /// 删除灰度分支，即放弃灰度。
+    pub async fn delete_branch(&self, request: OpenDeleteBranchRequest) -> ApolloClientResult<()> {
+        self.execute(request).await
+    }
+
+    /// 获取灰度规则。
+    pub async fn gray_rules(
+        &self,
+        request: OpenGetGrayRulesRequest,
+    ) -> ApolloClientResult<OpenGrayReleaseRuleResponse> {
+        self.execute(request).await
+    }
+
+    /// 更新灰度规则。
+    pub async fn update_gray_rules(
+        &self,
+        request: OpenUpdateGrayRulesRequest,
+    ) -> ApolloClientResult<()> {
+        self.execute(request).await
+    }
+
+    /// 灰度发布。
+    pub async fn publish_gray_release(
+        &self,
+        request: OpenGrayReleaseRequest,
+    ) -> ApolloClientResult<OpenPublishResponse> {
+        self.execute(request).await
+    }
+
+    /// 灰度全量发布，合并灰度分支到主版本并发布。
+    pub async fn merge_branch(
+        &self,
+        request: OpenMergeBranchRequest,
+    ) -> ApolloClientResult<OpenPublishResponse> {
+        self.execute(request).await
+    }
+
+    /// 创建App，需要apollo portal的版本高于`1.9`。
+    pub async fn create_app(&self, request: OpenCreateAppRequest) -> ApolloClientResult<()> {
+        self.execute(request).await
+    }
+
+    /// 创建集群接口。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_324-%e5%88%9b%e5%bb%ba%e9%9b%86%e7%be%a4%e6%8e%a5%e5%8f%a3)
+    pub async fn create_cluster(
+        &self,
+        request: OpenCreateClusterRequest,
+    ) -> ApolloClientResult<OpenClusterResponse> {
+        self.execute(request).await
+    }
+
+    /// 创建Namespace。
+    ///
+    /// [Ref](https://www.apolloconfig.com/#/zh/usage/apollo-open-api-platform?id=_327-%e5%88%9b%e5%bb%banamespace)
+    pub async fn create_namespace(
+        &self,
+        request: OpenCreateNamespaceRequest,
+    ) -> ApolloClientResult<OpenAppNamespaceResponse> {
+        self.execute(request).await
+    }
+
+    /// 关联公共Namespace，需要apollo portal的版本高于`1.9`。
+    pub async fn link_namespace(
+        &self,
+        request: OpenLinkNamespaceRequest,
+    ) -> ApolloClientResult<OpenNamespaceResponse> {
+        self.execute(request).await
+    }
+
     async fn execute<R: PerformResponse>(
         &self,
         request: impl PerformOpenRequest<Response = R>,
     ) -> ApolloClientResult<R> {
-        let url = handle_url(&request, self.portal_url.clone())?;
-        let mut request_builder = self.client.request(request.method(), url);
-        request_builder = request.request_builder(request_builder);
-        let response = request_builder.send().await?;
-        let response = validate_response(response).await?;
-        <R>::from_response(response).await
+        let mut request = build_request(&request, self.portal_url.clone())?;
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, self.token.clone());
+        let response = self.transport.send(request).await?;
+        let response = validate_response(response)?;
+        <R>::from_response(response)
     }
 }
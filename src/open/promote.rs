@@ -0,0 +1,191 @@
+//! Compare a namespace across environments, and promote the items from one environment to
+//! another, such as from `FAT` to `UAT`.
+
+use crate::{
+    errors::ApolloClientResult,
+    meta::DEFAULT_CLUSTER_NAME,
+    open::{
+        meta::{OpenRelease, ReleaseDiff},
+        requests::OpenGetNamespaceRequest,
+        responses::{OpenItemResponse, OpenPublishResponse},
+        sync::{DesiredItem, SyncPlan, SyncTarget},
+        OpenApiClient,
+    },
+};
+use futures_util::future::try_join;
+use std::collections::HashMap;
+
+/// The namespace to compare or promote, from one environment to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromoteTarget {
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub from_env: String,
+    pub to_env: String,
+}
+
+impl Default for PromoteTarget {
+    fn default() -> Self {
+        PromoteTarget {
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            from_env: "".to_string(),
+            to_env: "".to_string(),
+        }
+    }
+}
+
+impl PromoteTarget {
+    fn sync_target(&self, env: &str) -> SyncTarget {
+        SyncTarget {
+            env: env.to_string(),
+            app_id: self.app_id.clone(),
+            namespace_name: self.namespace_name.clone(),
+            cluster_name: self.cluster_name.clone(),
+        }
+    }
+}
+
+/// The result of [OpenApiClient::promote].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Promotion {
+    /// The applied changes of the items of the target environment.
+    pub plan: SyncPlan,
+    /// The release of the target environment, `None` if nothing changed.
+    pub release: Option<OpenPublishResponse>,
+}
+
+impl OpenApiClient {
+    /// Compare the items of the namespace in the two environments. The added keys are only in
+    /// `from_env`, the removed keys are only in `to_env`, and the changed values are in the
+    /// order of `(to_env, from_env)`, as if promoting all the items.
+    pub async fn compare_envs(&self, target: &PromoteTarget) -> ApolloClientResult<ReleaseDiff> {
+        let (from_items, to_items) = self.fetch_env_items(target).await?;
+        Ok(ReleaseDiff::new(
+            &configurations(&to_items),
+            &configurations(&from_items),
+        ))
+    }
+
+    /// Copy the values and comments of the keys from `from_env` to `to_env`, the keys absent in
+    /// `from_env` are deleted from `to_env`, then publish `to_env` with the release if anything
+    /// changed. The operator of the changes is the `released_by` of the release.
+    pub async fn promote(
+        &self,
+        target: &PromoteTarget,
+        keys: &[String],
+        release: OpenRelease,
+    ) -> ApolloClientResult<Promotion> {
+        let (from_items, to_items) = self.fetch_env_items(target).await?;
+        let plan = promotion_plan(
+            target.sync_target(&target.to_env),
+            keys,
+            from_items,
+            to_items,
+        )?;
+        let operator = release.released_by.clone();
+        let release = if plan.has_changes() {
+            plan.apply(self, &operator, Some(release)).await?
+        } else {
+            None
+        };
+        Ok(Promotion { plan, release })
+    }
+
+    async fn fetch_env_items(
+        &self,
+        target: &PromoteTarget,
+    ) -> ApolloClientResult<(Vec<OpenItemResponse>, Vec<OpenItemResponse>)> {
+        let fetch = |env: &str| {
+            self.get_namespace(OpenGetNamespaceRequest {
+                env: env.to_string(),
+                app_id: target.app_id.clone(),
+                namespace_name: target.namespace_name.clone(),
+                cluster_name: target.cluster_name.clone(),
+            })
+        };
+        let (from, to) = try_join(fetch(&target.from_env), fetch(&target.to_env)).await?;
+        Ok((from.items, to.items))
+    }
+}
+
+/// The items with empty key are the comment or blank lines of the `properties` namespace.
+fn configurations(items: &[OpenItemResponse]) -> HashMap<String, String> {
+    items
+        .iter()
+        .filter(|item| !item.key.is_empty())
+        .map(|item| (item.key.clone(), item.value.clone()))
+        .collect()
+}
+
+/// Keep the items of the target environment, except the promoted keys.
+fn promotion_plan(
+    target: SyncTarget,
+    keys: &[String],
+    from_items: Vec<OpenItemResponse>,
+    to_items: Vec<OpenItemResponse>,
+) -> ApolloClientResult<SyncPlan> {
+    let desired_item = |item: &OpenItemResponse| DesiredItem {
+        key: item.key.clone(),
+        value: item.value.clone(),
+        comment: item.comment.clone(),
+    };
+    let promoted = from_items
+        .iter()
+        .filter(|item| keys.contains(&item.key))
+        .map(desired_item);
+    let desired = to_items
+        .iter()
+        .filter(|item| !item.key.is_empty() && !keys.contains(&item.key))
+        .map(desired_item)
+        .chain(promoted)
+        .collect();
+    SyncPlan::new(target, desired, to_items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open::sync::{tests::live, SyncAction};
+
+    #[test]
+    fn test_configurations() {
+        let items = [live("", "", None), live("timeout", "100", None)];
+        assert_eq!(
+            configurations(&items),
+            HashMap::from([("timeout".to_string(), "100".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_promotion_plan() {
+        let from_items = vec![
+            live("timeout", "3000", None),
+            live("batch", "500", None),
+            live("port", "80", None),
+        ];
+        let to_items = vec![
+            live("", "", None),
+            live("timeout", "100", None),
+            live("batch", "200", None),
+            live("host", "a", None),
+        ];
+        let keys = ["timeout", "port", "host"].map(ToString::to_string);
+
+        let plan = promotion_plan(SyncTarget::default(), &keys, from_items, to_items).unwrap();
+        let changes = plan
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                SyncAction::Create(new) => Some(format!("+{}={}", new.key, new.value)),
+                SyncAction::Update { new, .. } => Some(format!("~{}={}", new.key, new.value)),
+                SyncAction::Delete(old) => Some(format!("-{}", old.key)),
+                SyncAction::NoOp(_) => None,
+            })
+            .collect::<Vec<_>>();
+        // The `batch` is not promoted.
+        assert_eq!(changes, ["~timeout=3000", "+port=80", "-host"]);
+    }
+}
@@ -2,17 +2,21 @@
 
 use crate::{
     errors::ApolloClientResult,
-    meta::{PerformRequest, DEFAULT_CLUSTER_NAME},
+    meta::{set_json_body, NamespaceKind, PerformRequest, DEFAULT_CLUSTER_NAME},
     open::{
-        meta::{OpenCreatedItem, OpenRelease, OpenUpdateItem},
+        meta::{
+            OpenCreatedApp, OpenCreatedItem, OpenGrayReleaseRuleItem, OpenRelease, OpenUpdateItem,
+        },
         responses::{
-            OpenAppResponse, OpenClusterResponse, OpenEnvClusterResponse, OpenItemResponse,
-            OpenNamespaceResponse, OpenPublishResponse,
+            OpenAppNamespaceResponse, OpenAppResponse, OpenClusterResponse, OpenEnvClusterResponse,
+            OpenGrayReleaseRuleResponse, OpenItemResponse, OpenNamespaceResponse, OpenPageResponse,
+            OpenPublishResponse,
         },
     },
+    transport::HttpRequest,
 };
 use http::Method;
-use reqwest::RequestBuilder;
+use serde::Serialize;
 use std::borrow::Cow;
 
 const OPEN_API_PREFIX: &str = "openapi/v1";
@@ -152,6 +156,43 @@ impl PerformRequest for OpenNamespaceRequest {
 
 impl PerformOpenRequest for OpenNamespaceRequest {}
 
+/// Fetch the info of a namespace.
+#[derive(Clone, Debug)]
+pub struct OpenGetNamespaceRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+}
+
+impl Default for OpenGetNamespaceRequest {
+    fn default() -> Self {
+        OpenGetNamespaceRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenGetNamespaceRequest {
+    type Response = OpenNamespaceResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenGetNamespaceRequest {}
+
 /// Create configuration item.
 #[derive(Debug, Clone)]
 pub struct OpenCreateItemRequest {
@@ -188,8 +229,8 @@ impl PerformRequest for OpenCreateItemRequest {
         Method::POST
     }
 
-    fn request_builder(&self, request_builder: RequestBuilder) -> RequestBuilder {
-        request_builder.json(&self.item)
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        set_json_body(request, &self.item)
     }
 
     fn app_id(&self) -> Option<&str> {
@@ -250,8 +291,8 @@ impl PerformRequest for OpenUpdateItemRequest {
         Ok(queries)
     }
 
-    fn request_builder(&self, request_builder: RequestBuilder) -> RequestBuilder {
-        request_builder.json(&self.item)
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        set_json_body(request, &self.item)
     }
 
     fn app_id(&self) -> Option<&str> {
@@ -261,6 +302,152 @@ impl PerformRequest for OpenUpdateItemRequest {
 
 impl PerformOpenRequest for OpenUpdateItemRequest {}
 
+/// Fetch configuration item.
+#[derive(Debug, Clone)]
+pub struct OpenGetItemRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub key: String,
+}
+
+impl Default for OpenGetItemRequest {
+    fn default() -> Self {
+        OpenGetItemRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            key: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenGetItemRequest {
+    type Response = OpenItemResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/items/{}",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.key,
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenGetItemRequest {}
+
+/// Delete configuration item.
+#[derive(Debug, Clone)]
+pub struct OpenDeleteItemRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub key: String,
+    pub operator: String,
+}
+
+impl Default for OpenDeleteItemRequest {
+    fn default() -> Self {
+        OpenDeleteItemRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            key: "".to_string(),
+            operator: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenDeleteItemRequest {
+    type Response = ();
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/items/{}",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.key,
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![("operator".into(), self.operator.as_str().into())])
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenDeleteItemRequest {}
+
+/// List configuration items by page, the page number starts from `0`.
+#[derive(Debug, Clone)]
+pub struct OpenListItemsRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub page: u32,
+    pub size: u32,
+}
+
+impl Default for OpenListItemsRequest {
+    fn default() -> Self {
+        OpenListItemsRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            page: 0,
+            size: 50,
+        }
+    }
+}
+
+impl PerformRequest for OpenListItemsRequest {
+    type Response = OpenPageResponse<OpenItemResponse>;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/items",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![
+            ("page".into(), self.page.to_string().into()),
+            ("size".into(), self.size.to_string().into()),
+        ])
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenListItemsRequest {}
+
 /// Publish a namespace.
 #[derive(Debug, Clone)]
 pub struct OpenPublishNamespaceRequest {
@@ -297,8 +484,8 @@ impl PerformRequest for OpenPublishNamespaceRequest {
         Method::POST
     }
 
-    fn request_builder(&self, request_builder: RequestBuilder) -> RequestBuilder {
-        request_builder.json(&self.release)
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        set_json_body(request, &self.release)
     }
 
     fn app_id(&self) -> Option<&str> {
@@ -307,3 +494,763 @@ impl PerformRequest for OpenPublishNamespaceRequest {
 }
 
 impl PerformOpenRequest for OpenPublishNamespaceRequest {}
+
+/// Fetch the latest active release of a namespace.
+#[derive(Debug, Clone)]
+pub struct OpenLatestReleaseRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+}
+
+impl Default for OpenLatestReleaseRequest {
+    fn default() -> Self {
+        OpenLatestReleaseRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenLatestReleaseRequest {
+    type Response = Option<OpenPublishResponse>;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/releases/latest",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenLatestReleaseRequest {}
+
+/// Fetch the releases of a namespace by page, the latest first, including the abandoned ones.
+#[derive(Debug, Clone)]
+pub struct OpenReleaseHistoryRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub page: u32,
+    pub size: u32,
+}
+
+impl Default for OpenReleaseHistoryRequest {
+    fn default() -> Self {
+        OpenReleaseHistoryRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            page: 0,
+            size: 50,
+        }
+    }
+}
+
+impl PerformRequest for OpenReleaseHistoryRequest {
+    type Response = OpenPageResponse<OpenPublishResponse>;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/releases",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![
+            ("page".into(), self.page.to_string().into()),
+            ("size".into(), self.size.to_string().into()),
+        ])
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenReleaseHistoryRequest {}
+
+/// Fetch a release by id.
+#[derive(Debug, Clone, Default)]
+pub struct OpenGetReleaseRequest {
+    pub env: String,
+    /// The id of [OpenPublishResponse].
+    pub release_id: u64,
+}
+
+impl PerformRequest for OpenGetReleaseRequest {
+    type Response = OpenPublishResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/releases/{}",
+            OPEN_API_PREFIX, self.env, self.release_id
+        )
+    }
+}
+
+impl PerformOpenRequest for OpenGetReleaseRequest {}
+
+/// Rollback a release, the previous active release of the namespace takes effect.
+#[derive(Debug, Clone, Default)]
+pub struct OpenRollbackReleaseRequest {
+    pub env: String,
+    /// The id of [OpenPublishResponse].
+    pub release_id: u64,
+    pub operator: String,
+}
+
+impl PerformRequest for OpenRollbackReleaseRequest {
+    type Response = ();
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/releases/{}/rollback",
+            OPEN_API_PREFIX, self.env, self.release_id
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![("operator".into(), self.operator.as_str().into())])
+    }
+}
+
+impl PerformOpenRequest for OpenRollbackReleaseRequest {}
+
+/// Create the gray release branch of a namespace.
+#[derive(Debug, Clone)]
+pub struct OpenCreateBranchRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub operator: String,
+}
+
+impl Default for OpenCreateBranchRequest {
+    fn default() -> Self {
+        OpenCreateBranchRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            operator: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenCreateBranchRequest {
+    type Response = OpenNamespaceResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![("operator".into(), self.operator.as_str().into())])
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenCreateBranchRequest {}
+
+/// Fetch the gray release branch of a namespace, the cluster name of the response is the
+/// branch name.
+#[derive(Debug, Clone)]
+pub struct OpenGetBranchRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+}
+
+impl Default for OpenGetBranchRequest {
+    fn default() -> Self {
+        OpenGetBranchRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenGetBranchRequest {
+    type Response = Option<OpenNamespaceResponse>;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name, self.namespace_name
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenGetBranchRequest {}
+
+/// Delete the gray release branch of a namespace.
+#[derive(Debug, Clone)]
+pub struct OpenDeleteBranchRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub branch_name: String,
+    pub operator: String,
+}
+
+impl Default for OpenDeleteBranchRequest {
+    fn default() -> Self {
+        OpenDeleteBranchRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            branch_name: "".to_string(),
+            operator: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenDeleteBranchRequest {
+    type Response = ();
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches/{}",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.branch_name,
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![("operator".into(), self.operator.as_str().into())])
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenDeleteBranchRequest {}
+
+/// Fetch the gray release rules of a branch.
+#[derive(Debug, Clone)]
+pub struct OpenGetGrayRulesRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub branch_name: String,
+}
+
+impl Default for OpenGetGrayRulesRequest {
+    fn default() -> Self {
+        OpenGetGrayRulesRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            branch_name: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenGetGrayRulesRequest {
+    type Response = OpenGrayReleaseRuleResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches/{}/rules",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.branch_name,
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenGetGrayRulesRequest {}
+
+/// Replace the gray release rules of a branch.
+#[derive(Debug, Clone)]
+pub struct OpenUpdateGrayRulesRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub branch_name: String,
+    pub rule_items: Vec<OpenGrayReleaseRuleItem>,
+    pub operator: String,
+}
+
+impl Default for OpenUpdateGrayRulesRequest {
+    fn default() -> Self {
+        OpenUpdateGrayRulesRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            branch_name: "".to_string(),
+            rule_items: vec![],
+            operator: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenUpdateGrayRulesRequest {
+    type Response = ();
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches/{}/rules",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.branch_name,
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![("operator".into(), self.operator.as_str().into())])
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Rules<'a> {
+            app_id: &'a str,
+            cluster_name: &'a str,
+            namespace_name: &'a str,
+            branch_name: &'a str,
+            rule_items: &'a [OpenGrayReleaseRuleItem],
+        }
+
+        set_json_body(
+            request,
+            &Rules {
+                app_id: &self.app_id,
+                cluster_name: &self.cluster_name,
+                namespace_name: &self.namespace_name,
+                branch_name: &self.branch_name,
+                rule_items: &self.rule_items,
+            },
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenUpdateGrayRulesRequest {}
+
+/// Publish the items of a branch as gray release.
+#[derive(Debug, Clone)]
+pub struct OpenGrayReleaseRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub branch_name: String,
+    pub release: OpenRelease,
+}
+
+impl Default for OpenGrayReleaseRequest {
+    fn default() -> Self {
+        OpenGrayReleaseRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            branch_name: "".to_string(),
+            release: Default::default(),
+        }
+    }
+}
+
+impl PerformRequest for OpenGrayReleaseRequest {
+    type Response = OpenPublishResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches/{}/releases",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.branch_name,
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        set_json_body(request, &self.release)
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenGrayReleaseRequest {}
+
+/// Merge the branch to the main namespace and publish, the branch is deleted if
+/// `delete_branch` is `true`.
+#[derive(Debug, Clone)]
+pub struct OpenMergeBranchRequest {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+    pub branch_name: String,
+    pub delete_branch: bool,
+    pub release: OpenRelease,
+}
+
+impl Default for OpenMergeBranchRequest {
+    fn default() -> Self {
+        OpenMergeBranchRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            branch_name: "".to_string(),
+            delete_branch: true,
+            release: Default::default(),
+        }
+    }
+}
+
+impl PerformRequest for OpenMergeBranchRequest {
+    type Response = OpenPublishResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces/{}/branches/{}/merge",
+            OPEN_API_PREFIX,
+            self.env,
+            self.app_id,
+            self.cluster_name,
+            self.namespace_name,
+            self.branch_name,
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn queries(&self) -> ApolloClientResult<Vec<(Cow<'_, str>, Cow<'_, str>)>> {
+        Ok(vec![(
+            "deleteBranch".into(),
+            self.delete_branch.to_string().into(),
+        )])
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        set_json_body(request, &self.release)
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenMergeBranchRequest {}
+
+/// Create an app, with the `application` namespace and the `default` cluster.
+///
+/// Requires the apollo portal newer than `1.9`, which provides the open api of creating apps.
+#[derive(Debug, Clone, Default)]
+pub struct OpenCreateAppRequest {
+    pub app: OpenCreatedApp,
+    /// Users who are assigned as the admins of the app.
+    pub admins: Vec<String>,
+    /// Whether assign the admin role to the consumer of the token.
+    pub assign_app_role_to_self: bool,
+}
+
+impl PerformRequest for OpenCreateAppRequest {
+    type Response = ();
+
+    fn path(&self) -> String {
+        format!("{}/apps", OPEN_API_PREFIX)
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct App<'a> {
+            assign_app_role_to_self: bool,
+            admins: &'a [String],
+            app: &'a OpenCreatedApp,
+        }
+
+        set_json_body(
+            request,
+            &App {
+                assign_app_role_to_self: self.assign_app_role_to_self,
+                admins: &self.admins,
+                app: &self.app,
+            },
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenCreateAppRequest {}
+
+/// Create a cluster of an app in the environment.
+#[derive(Debug, Clone, Default)]
+pub struct OpenCreateClusterRequest {
+    pub env: String,
+    pub app_id: String,
+    pub cluster_name: String,
+    pub data_change_created_by: String,
+}
+
+impl PerformRequest for OpenCreateClusterRequest {
+    type Response = OpenClusterResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters",
+            OPEN_API_PREFIX, self.env, self.app_id
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Cluster<'a> {
+            name: &'a str,
+            app_id: &'a str,
+            data_change_created_by: &'a str,
+        }
+
+        set_json_body(
+            request,
+            &Cluster {
+                name: &self.cluster_name,
+                app_id: &self.app_id,
+                data_change_created_by: &self.data_change_created_by,
+            },
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenCreateClusterRequest {}
+
+/// Create a namespace (AppNamespace) of an app.
+///
+/// The private namespace is created in all the environments and clusters of the app, the public
+/// namespace should be linked by [OpenLinkNamespaceRequest] to be used by other apps.
+#[derive(Debug, Clone)]
+pub struct OpenCreateNamespaceRequest {
+    pub app_id: String,
+    /// Name without the format suffix.
+    pub namespace_name: String,
+    pub format: NamespaceKind,
+    pub is_public: bool,
+    /// Whether prefix the name of the public namespace with the org id, such as
+    /// `FX.namespace`.
+    pub append_namespace_prefix: bool,
+    pub comment: Option<String>,
+    pub data_change_created_by: String,
+}
+
+impl Default for OpenCreateNamespaceRequest {
+    fn default() -> Self {
+        OpenCreateNamespaceRequest {
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            format: NamespaceKind::Properties,
+            is_public: false,
+            append_namespace_prefix: true,
+            comment: None,
+            data_change_created_by: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenCreateNamespaceRequest {
+    type Response = OpenAppNamespaceResponse;
+
+    fn path(&self) -> String {
+        format!("{}/apps/{}/appnamespaces", OPEN_API_PREFIX, self.app_id)
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AppNamespace<'a> {
+            name: &'a str,
+            app_id: &'a str,
+            format: String,
+            is_public: bool,
+            append_namespace_prefix: bool,
+            comment: &'a Option<String>,
+            data_change_created_by: &'a str,
+        }
+
+        set_json_body(
+            request,
+            &AppNamespace {
+                name: &self.namespace_name,
+                app_id: &self.app_id,
+                format: self.format.to_string(),
+                is_public: self.is_public,
+                append_namespace_prefix: self.append_namespace_prefix,
+                comment: &self.comment,
+                data_change_created_by: &self.data_change_created_by,
+            },
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenCreateNamespaceRequest {}
+
+/// Link a public namespace to the cluster of an app, so the app can override its
+/// configurations.
+///
+/// Requires the apollo portal newer than `1.9`, which provides the open api of linking
+/// namespaces.
+#[derive(Debug, Clone)]
+pub struct OpenLinkNamespaceRequest {
+    pub env: String,
+    pub app_id: String,
+    pub cluster_name: String,
+    /// Name of the public namespace.
+    pub namespace_name: String,
+    pub data_change_created_by: String,
+}
+
+impl Default for OpenLinkNamespaceRequest {
+    fn default() -> Self {
+        OpenLinkNamespaceRequest {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+            namespace_name: "".to_string(),
+            data_change_created_by: "".to_string(),
+        }
+    }
+}
+
+impl PerformRequest for OpenLinkNamespaceRequest {
+    type Response = OpenNamespaceResponse;
+
+    fn path(&self) -> String {
+        format!(
+            "{}/envs/{}/apps/{}/clusters/{}/namespaces",
+            OPEN_API_PREFIX, self.env, self.app_id, self.cluster_name
+        )
+    }
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn handle_request(&self, request: &mut HttpRequest) -> ApolloClientResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Namespace<'a> {
+            app_id: &'a str,
+            cluster_name: &'a str,
+            namespace_name: &'a str,
+            data_change_created_by: &'a str,
+        }
+
+        set_json_body(
+            request,
+            &Namespace {
+                app_id: &self.app_id,
+                cluster_name: &self.cluster_name,
+                namespace_name: &self.namespace_name,
+                data_change_created_by: &self.data_change_created_by,
+            },
+        )
+    }
+
+    fn app_id(&self) -> Option<&str> {
+        Some(&self.app_id)
+    }
+}
+
+impl PerformOpenRequest for OpenLinkNamespaceRequest {}
@@ -1,21 +1,25 @@
 //! open api responses.
 
+use crate::open::meta::{OpenGrayReleaseRuleItem, ReleaseDiff};
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// Define the response struct with the declared fields, which can have attributes, such as
+/// `#[serde(default)]`, and the base fields of apollo `BaseDTO`.
 macro_rules! struct_open_response_with_base_fields {
     (
         $(#[$enum_docs:meta])*
         $name:ident,
         {
-            $( ($i:ident, $t:ty) ,)*
+            $( $(#[$field_meta:meta])* ($i:ident, $t:ty) ,)*
         }
     ) => {
         $(#[$enum_docs])*
-        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
         #[serde(rename_all = "camelCase")]
         pub struct $name {
-            $(pub $i : $t,)*
+            $( $(#[$field_meta])* pub $i : $t,)*
             pub data_change_created_by: String,
             pub data_change_last_modified_by: String,
             pub data_change_created_by_display_name: Option<String>,
@@ -26,12 +30,14 @@ macro_rules! struct_open_response_with_base_fields {
     };
 }
 
+/// Same as `struct_open_response_with_base_fields`, with the leading fields locating the
+/// namespace.
 macro_rules! struct_open_response_with_namespace_fields {
     (
         $(#[$enum_docs:meta])*
         $name:ident,
         {
-            $( ($i:ident, $t:ty) ,)*
+            $( $(#[$field_meta:meta])* ($i:ident, $t:ty) ,)*
         }
     ) => {
         struct_open_response_with_base_fields! {
@@ -41,13 +47,14 @@ macro_rules! struct_open_response_with_namespace_fields {
                 (app_id, String),
                 (cluster_name, String),
                 (namespace_name, String),
+                $( $(#[$field_meta])* ($i, $t), )*
             }
         }
     };
 }
 
 /// Response for [crate::open::requests::OpenEnvClusterRequest].
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenEnvClusterResponse {
     pub env: String,
@@ -72,7 +79,8 @@ struct_open_response_with_base_fields! {
 implement_json_perform_response_for! { Vec<OpenAppResponse> }
 
 struct_open_response_with_namespace_fields! {
-    /// Response for [crate::open::requests::OpenNamespaceRequest].
+    /// Response for [crate::open::requests::OpenNamespaceRequest] and
+    /// [crate::open::requests::OpenGetNamespaceRequest].
     OpenNamespaceResponse,
     {
         (comment, Option<String>),
@@ -82,6 +90,7 @@ struct_open_response_with_namespace_fields! {
     }
 }
 
+implement_json_perform_response_for! { OpenNamespaceResponse }
 implement_json_perform_response_for! { Vec<OpenNamespaceResponse> }
 
 struct_open_response_with_base_fields! {
@@ -97,20 +106,64 @@ struct_open_response_with_base_fields! {
 implement_json_perform_response_for! { OpenItemResponse }
 implement_json_perform_response_for! { Vec<OpenItemResponse> }
 
+/// Page of the paginated responses, such as
+/// [crate::open::requests::OpenListItemsRequest].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenPageResponse<T> {
+    pub page: u32,
+    pub size: u32,
+    pub total: u64,
+    pub content: Vec<T>,
+}
+
+implement_json_perform_response_for! { OpenPageResponse<OpenItemResponse> }
+
 struct_open_response_with_namespace_fields! {
-    /// Response for [crate::open::requests::OpenPublishNamespaceRequest].
+    /// Response for [crate::open::requests::OpenPublishNamespaceRequest],
+    /// [crate::open::requests::OpenLatestReleaseRequest] and the other release requests.
     OpenPublishResponse,
     {
+        /// Absent in the responses of the old apollo, which can't be rolled back by the open
+        /// apis.
+        (id, Option<u64>),
         (name, String),
         (configurations, HashMap<String, String>),
         (comment, Option<String>),
+        /// Whether the release is rolled back.
+        #[serde(default)]
+        (is_abandoned, bool),
     }
 }
 
 implement_json_perform_response_for! { OpenPublishResponse }
+implement_json_perform_response_for! { OpenPageResponse<OpenPublishResponse> }
+
+impl OpenPublishResponse {
+    /// Compare the configurations of this release to the newer one.
+    pub fn diff(&self, newer: &OpenPublishResponse) -> ReleaseDiff {
+        ReleaseDiff::new(&self.configurations, &newer.configurations)
+    }
+}
 
 struct_open_response_with_base_fields! {
-    /// Response for [crate::open::requests::OpenClusterRequest].
+    /// Response for [crate::open::requests::OpenCreateNamespaceRequest].
+    OpenAppNamespaceResponse,
+    {
+        (name, String),
+        (app_id, String),
+        (format, String),
+        (is_public, bool),
+        (append_namespace_prefix, bool),
+        (comment, Option<String>),
+    }
+}
+
+implement_json_perform_response_for! { OpenAppNamespaceResponse }
+
+struct_open_response_with_base_fields! {
+    /// Response for [crate::open::requests::OpenClusterRequest] and
+    /// [crate::open::requests::OpenCreateClusterRequest].
     OpenClusterResponse,
     {
         (name, String),
@@ -119,3 +172,170 @@ struct_open_response_with_base_fields! {
 }
 
 implement_json_perform_response_for! { OpenClusterResponse }
+
+struct_open_response_with_namespace_fields! {
+    /// Response for [crate::open::requests::OpenGetGrayRulesRequest].
+    OpenGrayReleaseRuleResponse,
+    {
+        (branch_name, String),
+        (rule_items, Vec<OpenGrayReleaseRuleItem>),
+    }
+}
+
+implement_json_perform_response_for! { OpenGrayReleaseRuleResponse }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+    use std::fmt::Debug;
+
+    /// Deserialize the captured portal json, check that no field is dropped, and the serialized
+    /// json is deserialized to the same response.
+    fn round_trip<T>(fixture: &str) -> T
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let fixture = serde_json::from_str::<Value>(fixture).unwrap();
+        let response = serde_json::from_value::<T>(fixture.clone()).unwrap();
+        let serialized = serde_json::to_value(&response).unwrap();
+        assert_fields_kept(&fixture, &serialized, "$");
+        assert_eq!(serde_json::from_value::<T>(serialized).unwrap(), response);
+        response
+    }
+
+    fn assert_fields_kept(fixture: &Value, serialized: &Value, path: &str) {
+        match (fixture, serialized) {
+            (Value::Object(fixture), Value::Object(serialized)) => {
+                for (key, value) in fixture {
+                    let path = format!("{}.{}", path, key);
+                    match serialized.get(key) {
+                        Some(serialized) => assert_fields_kept(value, serialized, &path),
+                        None => panic!("field `{}` is dropped", path),
+                    }
+                }
+            }
+            (Value::Array(fixture), Value::Array(serialized)) => {
+                assert_eq!(fixture.len(), serialized.len(), "length of `{}`", path);
+                for (i, (fixture, serialized)) in fixture.iter().zip(serialized).enumerate() {
+                    assert_fields_kept(fixture, serialized, &format!("{}[{}]", path, i));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_env_cluster_response() {
+        let responses = round_trip::<Vec<OpenEnvClusterResponse>>(include_str!(
+            "../../tests/fixtures/open/env_clusters.json"
+        ));
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[2].env, "PRO");
+        assert_eq!(responses[2].clusters, ["default", "SHAOY", "SHAJQ"]);
+    }
+
+    #[test]
+    fn test_app_response() {
+        let responses =
+            round_trip::<Vec<OpenAppResponse>>(include_str!("../../tests/fixtures/open/apps.json"));
+        assert_eq!(responses[0].app_id, "100003171");
+        assert_eq!(responses[0].org_name, "研发部");
+    }
+
+    #[test]
+    fn test_cluster_response() {
+        let response = round_trip::<OpenClusterResponse>(include_str!(
+            "../../tests/fixtures/open/cluster.json"
+        ));
+        assert_eq!(response.name, "default");
+        assert_eq!(
+            response.data_change_created_time,
+            DateTime::parse_from_rfc3339("2018-12-23T12:35:16+08:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_namespace_response() {
+        let responses = round_trip::<Vec<OpenNamespaceResponse>>(include_str!(
+            "../../tests/fixtures/open/namespaces.json"
+        ));
+        assert_eq!(responses.len(), 2);
+
+        let namespace = &responses[1];
+        assert_eq!(namespace.namespace_name, "FX.apollo");
+        assert_eq!(
+            namespace.comment.as_deref(),
+            Some("apollo public namespace")
+        );
+        assert_eq!(namespace.format, "properties");
+        assert!(namespace.is_public);
+        assert_eq!(namespace.items.len(), 1);
+        assert_eq!(namespace.items[0].key, "request.timeout");
+        assert_eq!(
+            namespace.items[0]
+                .data_change_created_by_display_name
+                .as_deref(),
+            Some("Song")
+        );
+        assert_eq!(responses[0].items[0].comment, None);
+    }
+
+    #[test]
+    fn test_item_response() {
+        let response =
+            round_trip::<OpenItemResponse>(include_str!("../../tests/fixtures/open/item.json"));
+        assert_eq!(response.key, "timeout");
+        assert_eq!(response.comment.as_deref(), Some("超时时间"));
+
+        let page = round_trip::<OpenPageResponse<OpenItemResponse>>(include_str!(
+            "../../tests/fixtures/open/items_page.json"
+        ));
+        assert_eq!(page.total, 1);
+        assert_eq!(page.content, [response]);
+    }
+
+    #[test]
+    fn test_publish_response() {
+        let response = round_trip::<OpenPublishResponse>(include_str!(
+            "../../tests/fixtures/open/release.json"
+        ));
+        assert_eq!(response.id, Some(42));
+        assert_eq!(response.name, "2016-10-10");
+        assert_eq!(response.configurations["timeout"], "3000");
+        assert_eq!(response.comment.as_deref(), Some("修改timeout值"));
+
+        let mut value: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/open/release.json")).unwrap();
+        value.as_object_mut().unwrap().remove("id");
+        let response = serde_json::from_value::<OpenPublishResponse>(value).unwrap();
+        assert_eq!(response.id, None);
+    }
+
+    #[test]
+    fn test_app_namespace_response() {
+        let response = round_trip::<OpenAppNamespaceResponse>(include_str!(
+            "../../tests/fixtures/open/app_namespace.json"
+        ));
+        assert_eq!(response.name, "FX.public-0420-11");
+        assert!(response.is_public);
+        assert!(response.append_namespace_prefix);
+    }
+
+    #[test]
+    fn test_gray_release_rule_response() {
+        let response = round_trip::<OpenGrayReleaseRuleResponse>(include_str!(
+            "../../tests/fixtures/open/gray_rules.json"
+        ));
+        assert_eq!(response.branch_name, "20190408124608-8a1d6a4b8a6f2c41");
+        assert_eq!(
+            response.rule_items,
+            [OpenGrayReleaseRuleItem {
+                client_app_id: "100003171".to_string(),
+                client_ip_list: vec!["10.38.57.112".to_string()],
+                client_label_list: vec!["canary".to_string()],
+            }]
+        );
+    }
+}
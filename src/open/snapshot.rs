@@ -0,0 +1,291 @@
+//! Export the namespaces of an app cluster to a directory, and import them back, such as for
+//! disaster recovery or seeding local environments.
+//!
+//! The `properties` namespace is exported as the `{namespace}.properties` file, and the other
+//! namespaces are exported as the raw content, with the extension of the [NamespaceKind].
+
+use crate::{
+    errors::ApolloClientResult,
+    meta::NamespaceKind,
+    open::{
+        meta::OpenRelease,
+        requests::{OpenCreateNamespaceRequest, OpenNamespaceRequest},
+        responses::{OpenItemResponse, OpenNamespaceResponse},
+        sync::{DesiredItem, SyncAction, SyncPlan, SyncTarget},
+        OpenApiClient,
+    },
+    properties::Properties,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The item key of the whole content of the non `properties` namespaces.
+const CONTENT_KEY: &str = "content";
+
+impl OpenApiClient {
+    /// Export all namespaces of the app cluster to the directory, return the written files.
+    pub async fn export_namespaces(
+        &self,
+        request: OpenNamespaceRequest,
+        dir: impl AsRef<Path>,
+    ) -> ApolloClientResult<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let mut paths = Vec::new();
+        for namespace in self.namespace(request).await? {
+            let path = dir.join(file_name(&namespace));
+            fs::write(&path, file_content(&namespace))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Import the files of the directory to the app cluster, the files with unknown extension
+    /// are ignored. The missing namespaces are created, such as the `datasource.yaml` namespace
+    /// for the `datasource.yml` file, which is matched by the later imports. The missing and
+    /// different items are created or updated, and the items absent in the files are kept, so
+    /// are the comments of the live items. Then publish each changed namespace if the release is
+    /// specified. Return the applied plans.
+    pub async fn import_namespaces(
+        &self,
+        request: OpenNamespaceRequest,
+        dir: impl AsRef<Path>,
+        operator: &str,
+        release: Option<OpenRelease>,
+    ) -> ApolloClientResult<Vec<SyncPlan>> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Some((namespace_name, kind)) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(file_namespace)
+            {
+                files.push((namespace_name, kind, path));
+            }
+        }
+        files.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
+
+        let namespaces = self.namespace(request.clone()).await?;
+        let mut plans = Vec::new();
+        for (namespace_name, kind, path) in files {
+            let (namespace_name, live) = match live_namespace(&namespaces, &namespace_name, kind) {
+                Some(namespace) => (namespace.namespace_name.clone(), namespace.items.clone()),
+                None => {
+                    let name = base_name(&namespace_name, kind);
+                    self.create_namespace(OpenCreateNamespaceRequest {
+                        app_id: request.app_id.clone(),
+                        namespace_name: name.to_string(),
+                        format: kind,
+                        is_public: false,
+                        append_namespace_prefix: false,
+                        comment: None,
+                        data_change_created_by: operator.to_string(),
+                    })
+                    .await?;
+                    // Apollo suffixes the name with the format, such as `datasource.yaml` for
+                    // the `datasource.yml` file.
+                    let namespace_name = match kind {
+                        NamespaceKind::Properties => name.to_string(),
+                        _ => format!("{}.{}", name, kind),
+                    };
+                    (namespace_name, Vec::new())
+                }
+            };
+
+            let target = SyncTarget {
+                env: request.env.clone(),
+                app_id: request.app_id.clone(),
+                namespace_name,
+                cluster_name: request.cluster_name.clone(),
+            };
+            let desired = desired_items(kind, &fs::read_to_string(path)?, &live)?;
+            let mut plan = SyncPlan::new(target, desired, live)?;
+            plan.actions
+                .retain(|action| !matches!(action, SyncAction::Delete(_)));
+            if plan.has_changes() {
+                plan.apply(self, operator, release.clone()).await?;
+            }
+            plans.push(plan);
+        }
+        Ok(plans)
+    }
+}
+
+/// The kind of the namespace by the format, such as `datasource.json` or `datasource` with the
+/// `json` format.
+fn namespace_kind(namespace: &OpenNamespaceResponse) -> NamespaceKind {
+    NamespaceKind::infer_namespace_kind(&format!(
+        "{}.{}",
+        namespace.namespace_name, namespace.format
+    ))
+}
+
+fn file_name(namespace: &OpenNamespaceResponse) -> String {
+    let namespace_name = &namespace.namespace_name;
+    match namespace_kind(namespace) {
+        NamespaceKind::Properties => format!("{}.properties", namespace_name),
+        kind if NamespaceKind::infer_namespace_kind(namespace_name) == kind => {
+            namespace_name.clone()
+        }
+        kind => format!("{}.{}", namespace_name, kind),
+    }
+}
+
+fn file_content(namespace: &OpenNamespaceResponse) -> String {
+    match namespace_kind(namespace) {
+        NamespaceKind::Properties => namespace
+            .items
+            .iter()
+            .filter(|item| !item.key.is_empty())
+            .map(|item| (item.key.as_str(), item.value.as_str()))
+            .collect::<Properties>()
+            .to_string(),
+        _ => namespace
+            .items
+            .iter()
+            .find(|item| item.key == CONTENT_KEY)
+            .map(|item| item.value.clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// The namespace name and kind of the file, the `properties` namespace name is without suffix,
+/// the others are the file name, `None` if the extension is unknown.
+fn file_namespace(file_name: &str) -> Option<(String, NamespaceKind)> {
+    let (name, extension) = file_name.rsplit_once('.')?;
+    match NamespaceKind::infer_namespace_kind(file_name) {
+        NamespaceKind::Properties if extension == "properties" => {
+            Some((name.to_string(), NamespaceKind::Properties))
+        }
+        NamespaceKind::Properties => None,
+        kind => Some((file_name.to_string(), kind)),
+    }
+}
+
+/// The live namespace of the file, matched by the name, or by the name without the format
+/// suffix and the kind, such as the `datasource.yaml` namespace of the `datasource.yml` file.
+fn live_namespace<'a>(
+    namespaces: &'a [OpenNamespaceResponse],
+    namespace_name: &str,
+    kind: NamespaceKind,
+) -> Option<&'a OpenNamespaceResponse> {
+    namespaces
+        .iter()
+        .find(|namespace| namespace.namespace_name == namespace_name)
+        .or_else(|| {
+            namespaces.iter().find(|namespace| {
+                namespace_kind(namespace) == kind
+                    && base_name(&namespace.namespace_name, kind) == base_name(namespace_name, kind)
+            })
+        })
+}
+
+/// The namespace name without the format suffix.
+fn base_name(namespace_name: &str, kind: NamespaceKind) -> &str {
+    match kind {
+        NamespaceKind::Properties => namespace_name,
+        _ => namespace_name
+            .rsplit_once('.')
+            .map_or(namespace_name, |(name, _)| name),
+    }
+}
+
+/// The items of the file content, the files have no comments of items, so the comments of the
+/// live items are kept.
+fn desired_items(
+    kind: NamespaceKind,
+    content: &str,
+    live: &[OpenItemResponse],
+) -> ApolloClientResult<Vec<DesiredItem>> {
+    let desired_item = |key: String, value: String| DesiredItem {
+        comment: live
+            .iter()
+            .find(|item| item.key == key)
+            .and_then(|item| item.comment.clone()),
+        key,
+        value,
+    };
+    Ok(match kind {
+        NamespaceKind::Properties => Properties::parse(content)?
+            .into_iter()
+            .map(|(key, value)| desired_item(key, value))
+            .collect(),
+        _ if content.is_empty() => Vec::new(),
+        _ => vec![desired_item(CONTENT_KEY.to_string(), content.to_string())],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open::sync::tests::live;
+
+    #[test]
+    fn test_file_namespace() {
+        assert_eq!(
+            file_namespace("application.properties"),
+            Some(("application".to_string(), NamespaceKind::Properties))
+        );
+        assert_eq!(
+            file_namespace("datasource.json"),
+            Some(("datasource.json".to_string(), NamespaceKind::Json))
+        );
+        assert_eq!(
+            file_namespace("datasource.yml"),
+            Some(("datasource.yml".to_string(), NamespaceKind::Yaml))
+        );
+        assert_eq!(file_namespace("README.md"), None);
+        assert_eq!(file_namespace("application"), None);
+    }
+
+    #[test]
+    fn test_base_name() {
+        assert_eq!(
+            base_name("application", NamespaceKind::Properties),
+            "application"
+        );
+        assert_eq!(
+            base_name("datasource.json", NamespaceKind::Json),
+            "datasource"
+        );
+    }
+
+    #[test]
+    fn test_desired_items() {
+        let live = [live("timeout", "50", Some("timeout in milliseconds"))];
+        let items = desired_items(
+            NamespaceKind::Properties,
+            "timeout=100\n# comment\nbatch=200\n",
+            &live,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|item| (item.key, item.value, item.comment))
+        .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            [
+                (
+                    "timeout".to_string(),
+                    "100".to_string(),
+                    Some("timeout in milliseconds".to_string())
+                ),
+                ("batch".to_string(), "200".to_string(), None),
+            ]
+        );
+
+        let items = desired_items(NamespaceKind::Json, r#"{"timeout": 100}"#, &[]).unwrap();
+        assert_eq!(items[0].key, "content");
+        assert_eq!(items[0].value, r#"{"timeout": 100}"#);
+        assert!(desired_items(NamespaceKind::Json, "", &[])
+            .unwrap()
+            .is_empty());
+    }
+}
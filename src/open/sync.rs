@@ -0,0 +1,404 @@
+//! Declarative sync of namespace items.
+//!
+//! Compute the [SyncPlan] from the desired items and the live items of a namespace, then apply
+//! it, like terraform:
+//!
+//! ```no_run
+//! use apollo_client::open::{
+//!     meta::OpenRelease,
+//!     sync::{DesiredItem, SyncPlan, SyncTarget},
+//!     OpenApiClientBuilder,
+//! };
+//! use std::error::Error;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn Error>> {
+//!     let client = OpenApiClientBuilder::new(
+//!         "http://127.0.0.1:8070/".parse()?,
+//!         "391cc4053f8cce2e452a0e6db8925bbba503f434",
+//!     )?
+//!     .build()?;
+//!
+//!     // Such as the items kept in git.
+//!     let desired: Vec<DesiredItem> = serde_json::from_str(
+//!         r#"[{"key": "timeout", "value": "3000", "comment": "timeout in milliseconds"}]"#,
+//!     )?;
+//!     let target = SyncTarget {
+//!         env: "DEV".to_string(),
+//!         app_id: "SampleApp".to_string(),
+//!         namespace_name: "application".to_string(),
+//!         ..Default::default()
+//!     };
+//!
+//!     let plan = SyncPlan::fetch(&client, target, desired).await?;
+//!     println!("{}", plan);
+//!
+//!     let release = OpenRelease {
+//!         release_title: "sync".to_string(),
+//!         released_by: "apollo".to_string(),
+//!         ..Default::default()
+//!     };
+//!     plan.apply(&client, "apollo", Some(release)).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    errors::{ApolloClientError, ApolloClientResult},
+    meta::DEFAULT_CLUSTER_NAME,
+    open::{
+        meta::{OpenCreatedItem, OpenRelease, OpenUpdateItem},
+        requests::{
+            OpenCreateItemRequest, OpenDeleteItemRequest, OpenGetNamespaceRequest,
+            OpenPublishNamespaceRequest, OpenUpdateItemRequest,
+        },
+        responses::{OpenItemResponse, OpenPublishResponse},
+        OpenApiClient,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+/// The namespace to sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncTarget {
+    pub env: String,
+    pub app_id: String,
+    pub namespace_name: String,
+    pub cluster_name: String,
+}
+
+impl Default for SyncTarget {
+    fn default() -> Self {
+        SyncTarget {
+            env: "".to_string(),
+            app_id: "".to_string(),
+            namespace_name: "".to_string(),
+            cluster_name: DEFAULT_CLUSTER_NAME.to_string(),
+        }
+    }
+}
+
+/// The desired state of an item.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesiredItem {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// The action to sync an item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// The item is not live.
+    Create(DesiredItem),
+    /// The value or comment of the live item differs.
+    Update {
+        old: OpenItemResponse,
+        new: DesiredItem,
+    },
+    /// The live item is not desired.
+    Delete(OpenItemResponse),
+    /// The live item is already desired.
+    NoOp(OpenItemResponse),
+}
+
+impl SyncAction {
+    /// The key of the item.
+    pub fn key(&self) -> &str {
+        match self {
+            SyncAction::Create(item) => &item.key,
+            SyncAction::Update { new, .. } => &new.key,
+            SyncAction::Delete(item) | SyncAction::NoOp(item) => &item.key,
+        }
+    }
+
+    /// Whether the item is already desired.
+    pub fn is_no_op(&self) -> bool {
+        matches!(self, SyncAction::NoOp(_))
+    }
+}
+
+/// The actions to sync the live items of the namespace to the desired items, the desired items
+/// first, then the items to delete.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncPlan {
+    pub target: SyncTarget,
+    pub actions: Vec<SyncAction>,
+}
+
+impl SyncPlan {
+    /// Compute the plan, the live items with empty key, which are the comment or blank lines of
+    /// the `properties` namespace, are kept.
+    pub fn new(
+        target: SyncTarget,
+        desired: Vec<DesiredItem>,
+        live: Vec<OpenItemResponse>,
+    ) -> ApolloClientResult<Self> {
+        let mut keys = HashSet::new();
+        for item in &desired {
+            if !keys.insert(item.key.as_str()) {
+                return Err(ApolloClientError::DuplicateKey(item.key.clone()));
+            }
+        }
+
+        let (mut live, deleted): (Vec<_>, Vec<_>) = live
+            .into_iter()
+            .filter(|item| !item.key.is_empty())
+            .partition(|item| keys.contains(item.key.as_str()));
+
+        let mut actions = desired
+            .into_iter()
+            .map(
+                |new| match live.iter().position(|item| item.key == new.key) {
+                    Some(index) => {
+                        let old = live.swap_remove(index);
+                        if old.value == new.value
+                            && non_empty(&old.comment) == non_empty(&new.comment)
+                        {
+                            SyncAction::NoOp(old)
+                        } else {
+                            SyncAction::Update { old, new }
+                        }
+                    }
+                    None => SyncAction::Create(new),
+                },
+            )
+            .collect::<Vec<_>>();
+        actions.extend(deleted.into_iter().map(SyncAction::Delete));
+
+        Ok(SyncPlan { target, actions })
+    }
+
+    /// Fetch the live items of the namespace and compute the plan.
+    pub async fn fetch(
+        client: &OpenApiClient,
+        target: SyncTarget,
+        desired: Vec<DesiredItem>,
+    ) -> ApolloClientResult<Self> {
+        let namespace = client
+            .get_namespace(OpenGetNamespaceRequest {
+                env: target.env.clone(),
+                app_id: target.app_id.clone(),
+                namespace_name: target.namespace_name.clone(),
+                cluster_name: target.cluster_name.clone(),
+            })
+            .await?;
+        Self::new(target, desired, namespace.items)
+    }
+
+    /// Whether any item needs to change.
+    pub fn has_changes(&self) -> bool {
+        self.actions.iter().any(|action| !action.is_no_op())
+    }
+
+    /// Apply the actions by the operator, then publish the namespace if the release is
+    /// specified, even though there is no change, to publish the changes made by others.
+    pub async fn apply(
+        &self,
+        client: &OpenApiClient,
+        operator: &str,
+        release: Option<OpenRelease>,
+    ) -> ApolloClientResult<Option<OpenPublishResponse>> {
+        let target = &self.target;
+        for action in &self.actions {
+            match action {
+                SyncAction::Create(item) => {
+                    client
+                        .create_item(OpenCreateItemRequest {
+                            env: target.env.clone(),
+                            app_id: target.app_id.clone(),
+                            namespace_name: target.namespace_name.clone(),
+                            cluster_name: target.cluster_name.clone(),
+                            item: OpenCreatedItem {
+                                key: item.key.clone(),
+                                value: item.value.clone(),
+                                comment: item.comment.clone(),
+                                data_change_created_by: operator.to_string(),
+                            },
+                        })
+                        .await?;
+                }
+                SyncAction::Update { new, .. } => {
+                    client
+                        .update_item(OpenUpdateItemRequest {
+                            env: target.env.clone(),
+                            app_id: target.app_id.clone(),
+                            namespace_name: target.namespace_name.clone(),
+                            cluster_name: target.cluster_name.clone(),
+                            create_if_not_exists: false,
+                            item: OpenUpdateItem {
+                                key: new.key.clone(),
+                                value: new.value.clone(),
+                                comment: new.comment.clone(),
+                                data_change_created_by: None,
+                                data_change_last_modified_by: operator.to_string(),
+                            },
+                        })
+                        .await?;
+                }
+                SyncAction::Delete(item) => {
+                    client
+                        .delete_item(OpenDeleteItemRequest {
+                            env: target.env.clone(),
+                            app_id: target.app_id.clone(),
+                            namespace_name: target.namespace_name.clone(),
+                            cluster_name: target.cluster_name.clone(),
+                            key: item.key.clone(),
+                            operator: operator.to_string(),
+                        })
+                        .await?;
+                }
+                SyncAction::NoOp(_) => {}
+            }
+        }
+
+        match release {
+            Some(release) => client
+                .publish_namespace(OpenPublishNamespaceRequest {
+                    env: target.env.clone(),
+                    app_id: target.app_id.clone(),
+                    namespace_name: target.namespace_name.clone(),
+                    cluster_name: target.cluster_name.clone(),
+                    release,
+                })
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Show the changes like `terraform plan`, the no-ops are omitted.
+impl Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for action in &self.actions {
+            match action {
+                SyncAction::Create(item) => writeln!(f, "+ {} = {:?}", item.key, item.value)?,
+                SyncAction::Update { old, new } if old.value == new.value => {
+                    writeln!(f, "~ {} (comment)", new.key)?
+                }
+                SyncAction::Update { old, new } => {
+                    writeln!(f, "~ {} = {:?} -> {:?}", new.key, old.value, new.value)?
+                }
+                SyncAction::Delete(item) => writeln!(f, "- {}", item.key)?,
+                SyncAction::NoOp(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Apollo responds empty comment for the items without comment.
+fn non_empty(comment: &Option<String>) -> Option<&str> {
+    comment.as_deref().filter(|comment| !comment.is_empty())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use chrono::Local;
+
+    /// The live item modified by `apollo` now.
+    pub(crate) fn live(key: &str, value: &str, comment: Option<&str>) -> OpenItemResponse {
+        OpenItemResponse {
+            key: key.to_string(),
+            value: value.to_string(),
+            comment: comment.map(ToString::to_string),
+            data_change_created_by: "apollo".to_string(),
+            data_change_last_modified_by: "apollo".to_string(),
+            data_change_created_by_display_name: None,
+            data_change_last_modified_by_display_name: None,
+            data_change_created_time: Local::now(),
+            data_change_last_modified_time: Local::now(),
+        }
+    }
+
+    fn desired(key: &str, value: &str, comment: Option<&str>) -> DesiredItem {
+        DesiredItem {
+            key: key.to_string(),
+            value: value.to_string(),
+            comment: comment.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let plan = SyncPlan::new(
+            SyncTarget::default(),
+            vec![
+                desired("timeout", "3000", None),
+                desired("batch", "200", Some("batch size")),
+                desired("host", "a", None),
+                desired("port", "80", None),
+            ],
+            vec![
+                live("", "", Some("# comment line")),
+                live("timeout", "100", None),
+                live("batch", "200", None),
+                live("host", "a", Some("")),
+                live("retry", "3", None),
+            ],
+        )
+        .unwrap();
+
+        let actions = plan
+            .actions
+            .iter()
+            .map(|action| {
+                let kind = match action {
+                    SyncAction::Create(_) => "create",
+                    SyncAction::Update { .. } => "update",
+                    SyncAction::Delete(_) => "delete",
+                    SyncAction::NoOp(_) => "no-op",
+                };
+                (action.key(), kind)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            [
+                ("timeout", "update"),
+                ("batch", "update"),
+                ("host", "no-op"),
+                ("port", "create"),
+                ("retry", "delete"),
+            ]
+        );
+        assert!(plan.has_changes());
+        assert_eq!(
+            plan.to_string(),
+            "~ timeout = \"100\" -> \"3000\"\n~ batch (comment)\n+ port = \"80\"\n- retry\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_no_changes() {
+        let plan = SyncPlan::new(
+            SyncTarget::default(),
+            vec![desired("timeout", "100", None)],
+            vec![live("timeout", "100", None)],
+        )
+        .unwrap();
+        assert!(!plan.has_changes());
+        assert_eq!(plan.to_string(), "");
+    }
+
+    #[test]
+    fn test_plan_duplicate_key() {
+        let result = SyncPlan::new(
+            SyncTarget::default(),
+            vec![
+                desired("timeout", "100", None),
+                desired("timeout", "200", None),
+            ],
+            vec![],
+        );
+        assert!(matches!(result, Err(ApolloClientError::DuplicateKey(key)) if key == "timeout"));
+    }
+}
@@ -0,0 +1,435 @@
+//! Java `.properties` format parser and serializer, follows the semantics of
+//! `java.util.Properties#load` and `java.util.Properties#store`.
+//!
+//! Ref: <https://docs.oracle.com/javase/8/docs/api/java/util/Properties.html#load-java.io.Reader->.
+//!
+//! # Example
+//!
+//! ```
+//! use apollo_client::properties::Properties;
+//!
+//! let properties: Properties = "# comment\nkey1 = value1\nkey2:hello \\\n    world\nkey3=\\u4f60\\u597d"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(properties.get("key1"), Some("value1"));
+//! assert_eq!(properties.get("key2"), Some("hello world"));
+//! assert_eq!(properties.get("key3"), Some("你好"));
+//!
+//! assert_eq!(
+//!     properties.to_string(),
+//!     "key1=value1\nkey2=hello world\nkey3=你好\n"
+//! );
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+/// Error occurred when parsing properties.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("malformed \\uxxxx encoding at line {line}")]
+pub struct ParseError {
+    /// The line number (start from 1) of the logical line.
+    pub line: usize,
+}
+
+/// Key value pairs of java properties, keep the insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct Properties {
+    entries: Vec<(String, String)>,
+    indexes: HashMap<String, usize>,
+}
+
+impl Properties {
+    /// Create an empty properties.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse the content in java properties format.
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        let mut properties = Self::new();
+        for (line_number, line) in logical_lines(content) {
+            let (key, value) = split_key_value(&line);
+            let key = unescape(key).ok_or(ParseError { line: line_number })?;
+            let value = unescape(value).ok_or(ParseError { line: line_number })?;
+            properties.insert(key, value);
+        }
+        Ok(properties)
+    }
+
+    /// Insert the key value pair, return the old value if the key exists, the position of the
+    /// key is kept.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        let key = key.into();
+        let value = value.into();
+        match self.indexes.get(&key) {
+            Some(&index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.indexes.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Get the value of the key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.indexes
+            .get(key)
+            .map(|&index| self.entries[index].1.as_str())
+    }
+
+    /// Whether the key exists.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.indexes.contains_key(key)
+    }
+
+    /// Remove the key, return the value if the key exists.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.indexes.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+        for entry_index in self.indexes.values_mut() {
+            if *entry_index > index {
+                *entry_index -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    /// Count of the key value pairs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no key value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate the key value pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Iterate the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+}
+
+impl PartialEq for Properties {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Eq for Properties {}
+
+impl FromStr for Properties {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Serialize in java properties format, one `key=value` per line, without the date comment
+/// written by `java.util.Properties#store`.
+impl Display for Properties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in self.iter() {
+            escape(f, key, true)?;
+            f.write_char('=')?;
+            escape(f, value, false)?;
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Properties {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut properties = Self::new();
+        properties.extend(iter);
+        properties
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Properties {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl IntoIterator for Properties {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[inline]
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\x0c')
+}
+
+/// Join the natural lines into logical lines, skip blank lines and comment lines, with the line
+/// number of the first natural line.
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = vec![];
+    let mut current: Option<(usize, String)> = None;
+
+    // Line terminators are `\n`, `\r` or `\r\n`.
+    let content = content.replace("\r\n", "\n");
+    for (index, natural_line) in content.split(['\n', '\r']).enumerate() {
+        let natural_line = natural_line.trim_start_matches(is_whitespace);
+
+        let (line_number, mut line) = match current.take() {
+            Some(current) => current,
+            None => {
+                if natural_line.is_empty()
+                    || natural_line.starts_with('#')
+                    || natural_line.starts_with('!')
+                {
+                    continue;
+                }
+                (index + 1, String::new())
+            }
+        };
+
+        let trailing_backslashes = natural_line.len() - natural_line.trim_end_matches('\\').len();
+        if trailing_backslashes % 2 == 1 {
+            line.push_str(&natural_line[..natural_line.len() - 1]);
+            current = Some((line_number, line));
+        } else {
+            line.push_str(natural_line);
+            lines.push((line_number, line));
+        }
+    }
+
+    lines.extend(current);
+    lines
+}
+
+/// Split the logical line into the escaped key and value.
+fn split_key_value(line: &str) -> (&str, &str) {
+    let mut key_end = line.len();
+    let mut value_start = line.len();
+    let mut has_separator = false;
+    let mut preceding_backslash = false;
+
+    for (index, c) in line.char_indices() {
+        if !preceding_backslash && (c == '=' || c == ':' || is_whitespace(c)) {
+            key_end = index;
+            value_start = index + c.len_utf8();
+            has_separator = c == '=' || c == ':';
+            break;
+        }
+        preceding_backslash = c == '\\' && !preceding_backslash;
+    }
+
+    let rest = &line[value_start..];
+    let mut value_offset = rest.len();
+    for (index, c) in rest.char_indices() {
+        if is_whitespace(c) {
+            continue;
+        }
+        if !has_separator && (c == '=' || c == ':') {
+            has_separator = true;
+            continue;
+        }
+        value_offset = index;
+        break;
+    }
+
+    (&line[..key_end], &rest[value_offset..])
+}
+
+/// Unescape the key or value, return `None` if the `\uxxxx` encoding is malformed.
+fn unescape(s: &str) -> Option<String> {
+    let mut units = Vec::<u16>::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 2];
+            units.extend_from_slice(c.encode_utf16(&mut buf));
+            continue;
+        }
+
+        match chars.next() {
+            Some('u') => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                if hex.len() != 4 {
+                    return None;
+                }
+                units.push(u16::from_str_radix(&hex, 16).ok()?);
+            }
+            Some('t') => units.push('\t' as u16),
+            Some('r') => units.push('\r' as u16),
+            Some('n') => units.push('\n' as u16),
+            Some('f') => units.push('\x0c' as u16),
+            Some(c) => {
+                let mut buf = [0; 2];
+                units.extend_from_slice(c.encode_utf16(&mut buf));
+            }
+            // The single trailing backslash is dropped.
+            None => {}
+        }
+    }
+    Some(String::from_utf16_lossy(&units))
+}
+
+fn escape(f: &mut fmt::Formatter<'_>, s: &str, is_key: bool) -> fmt::Result {
+    for (index, c) in s.chars().enumerate() {
+        match c {
+            ' ' if index == 0 || is_key => f.write_str("\\ ")?,
+            '\\' => f.write_str("\\\\")?,
+            '\t' => f.write_str("\\t")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\x0c' => f.write_str("\\f")?,
+            '=' | ':' | '#' | '!' => {
+                f.write_char('\\')?;
+                f.write_char(c)?;
+            }
+            c if c.is_control() && (c as u32) < 0x10000 => write!(f, "\\u{:04X}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Vec<(String, String)> {
+        Properties::parse(content).unwrap().into_iter().collect()
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_separators() {
+        assert_eq!(
+            parse("a=1\nb:2\nc 3\nd\t=\t4\ne  :  5\nf\ng =\nh = = 6\ni::7"),
+            pairs(&[
+                ("a", "1"),
+                ("b", "2"),
+                ("c", "3"),
+                ("d", "4"),
+                ("e", "5"),
+                ("f", ""),
+                ("g", ""),
+                ("h", "= 6"),
+                ("i", ":7"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_comments_and_blank_lines() {
+        assert_eq!(
+            parse("# comment\n! comment\n\n   \n  # indented comment\na=1 # not comment\n"),
+            pairs(&[("a", "1 # not comment")])
+        );
+    }
+
+    #[test]
+    fn test_parse_continuation() {
+        assert_eq!(
+            parse("fruits apple, banana, \\\n    pear, \\\r\n  #cantaloupe\na=b\\\\\nc=d"),
+            pairs(&[
+                ("fruits", "apple, banana, pear, #cantaloupe"),
+                ("a", "b\\"),
+                ("c", "d"),
+            ])
+        );
+        assert_eq!(parse("a=1\\\n\nb=2\\"), pairs(&[("a", "1"), ("b", "2")]));
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        assert_eq!(
+            parse(
+                "key\\ with\\ spaces=v\na\\=b\\:c=d\n[section]=x\nu=\\u4f60\\u597d\\uD83D\\uDE00\n\
+                 t=\\t\\n\\r\\f\\x"
+            ),
+            pairs(&[
+                ("key with spaces", "v"),
+                ("a=b:c", "d"),
+                ("[section]", "x"),
+                ("u", "你好😀"),
+                ("t", "\t\n\r\x0cx"),
+            ])
+        );
+        assert_eq!(Properties::parse("a=\\u12"), Err(ParseError { line: 1 }));
+        assert_eq!(
+            Properties::parse("a=1\nb=\\\n\\uzzzz"),
+            Err(ParseError { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let properties = [
+            ("key with spaces", " leading space"),
+            ("a=b:c#d!e", "x=y:z"),
+            ("multi", "line1\nline2\tend\\"),
+            ("unicode", "你好\u{1}"),
+            ("", "empty key"),
+        ]
+        .into_iter()
+        .collect::<Properties>();
+
+        let content = properties.to_string();
+        assert_eq!(
+            content,
+            "key\\ with\\ spaces=\\ leading space\n\
+             a\\=b\\:c\\#d\\!e=x\\=y\\:z\n\
+             multi=line1\\nline2\\tend\\\\\n\
+             unicode=你好\\u0001\n\
+             =empty key\n"
+        );
+        assert_eq!(Properties::parse(&content).unwrap(), properties);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut properties = Properties::new();
+        assert_eq!(properties.insert("a", "1"), None);
+        assert_eq!(properties.insert("b", "2"), None);
+        assert_eq!(properties.insert("c", "3"), None);
+        assert_eq!(properties.insert("a", "10"), Some("1".to_string()));
+        assert_eq!(properties.remove("b"), Some("2".to_string()));
+        assert_eq!(properties.remove("b"), None);
+        assert_eq!(properties.get("c"), Some("3"));
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["a", "c"]);
+        assert_eq!(properties.len(), 2);
+        assert_eq!(
+            properties,
+            [("c", "3"), ("a", "10")]
+                .into_iter()
+                .collect::<Properties>()
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Handlers of the mock config service.
+
+use super::{json_response, text_response, MockApollo, Namespace, Route, MOCK_ENV};
+use crate::{
+    conf::{
+        meta::{ApolloNotificationMessages, Notification},
+        responses::{FetchResponse, ServiceResponse},
+    },
+    errors::ApolloClientResult,
+    properties::Properties,
+    transport::{HttpRequest, HttpResponse, Timeout},
+};
+use http::StatusCode;
+use std::collections::HashMap;
+use tokio::time::{timeout_at, Instant};
+
+impl MockApollo {
+    pub(super) fn handle_fetch(
+        &self,
+        route: &Route,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        let release = match store
+            .namespace(MOCK_ENV, app_id, cluster_name, namespace_name)
+            .and_then(Namespace::release)
+        {
+            Some(release) => release,
+            None => return text_response(StatusCode::NOT_FOUND, "Not Found"),
+        };
+
+        if route.query("releaseKey") == Some(&release.release_key) {
+            return text_response(StatusCode::NOT_MODIFIED, "");
+        }
+
+        json_response(
+            StatusCode::OK,
+            &FetchResponse {
+                app_id: app_id.to_string(),
+                cluster: cluster_name.to_string(),
+                namespace_name: namespace_name.to_string(),
+                configurations: release.configurations.clone(),
+                release_key: release.release_key.clone(),
+                from_cache: false,
+            },
+        )
+    }
+
+    pub(super) fn handle_cached_fetch(
+        &self,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        match store
+            .namespace(MOCK_ENV, app_id, cluster_name, namespace_name)
+            .and_then(Namespace::release)
+        {
+            Some(release) => {
+                let properties = release
+                    .configurations
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect::<Properties>();
+                text_response(StatusCode::OK, &properties.to_string())
+            }
+            None => text_response(StatusCode::NOT_FOUND, "Not Found"),
+        }
+    }
+
+    pub(super) fn handle_config_services(&self) -> ApolloClientResult<HttpResponse> {
+        let services = self
+            .store()
+            .config_services
+            .iter()
+            .enumerate()
+            .map(|(index, url)| ServiceResponse {
+                app_name: "APOLLO-CONFIGSERVICE".to_string(),
+                instance_id: format!("apollo-configservice-{}", index),
+                homepage_url: url.to_string(),
+            })
+            .collect::<Vec<_>>();
+        json_response(StatusCode::OK, &services)
+    }
+
+    /// Respond the changed namespaces immediately, otherwise hold the request until released,
+    /// or respond `304` after the long poll timeout.
+    pub(super) async fn handle_notify(
+        &self,
+        request: &HttpRequest,
+        route: &Route,
+    ) -> ApolloClientResult<HttpResponse> {
+        let app_id = route.query("appId").unwrap_or_default();
+        let cluster_name = route.query("cluster").unwrap_or_default();
+        let notifications: Vec<Notification> =
+            match serde_json::from_str(route.query("notifications").unwrap_or_default()) {
+                Ok(notifications) => notifications,
+                Err(_) => return text_response(StatusCode::BAD_REQUEST, "Bad Request"),
+            };
+
+        let mut timeout = self.store().long_poll_timeout;
+        if let Some(Timeout(request_timeout)) = request.extensions().get::<Timeout>() {
+            timeout = timeout.min(*request_timeout);
+        }
+        let deadline = Instant::now() + timeout;
+
+        // Subscribe before checking, to not miss the release between checking and waiting.
+        let mut version = self.inner.version.subscribe();
+        loop {
+            let changed = self.changed_notifications(app_id, cluster_name, &notifications);
+            if !changed.is_empty() {
+                return json_response(StatusCode::OK, &changed);
+            }
+            match timeout_at(deadline, version.changed()).await {
+                Ok(Ok(())) => continue,
+                _ => return text_response(StatusCode::NOT_MODIFIED, ""),
+            }
+        }
+    }
+
+    fn changed_notifications(
+        &self,
+        app_id: &str,
+        cluster_name: &str,
+        notifications: &[Notification],
+    ) -> Vec<Notification> {
+        let store = self.store();
+        notifications
+            .iter()
+            .filter_map(|notification| {
+                let notification_id = store
+                    .namespace(MOCK_ENV, app_id, cluster_name, &notification.namespace_name)?
+                    .notification_id();
+                if notification_id == notification.notification_id || notification_id < 0 {
+                    return None;
+                }
+
+                let namespace_name = notification
+                    .namespace_name
+                    .strip_suffix(".properties")
+                    .unwrap_or(&notification.namespace_name);
+                let key = format!("{}+{}+{}", app_id, cluster_name, namespace_name);
+                Some(Notification {
+                    namespace_name: notification.namespace_name.clone(),
+                    notification_id,
+                    messages: Some(ApolloNotificationMessages {
+                        details: HashMap::from([(key, notification_id as i64)]),
+                    }),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,553 @@
+//! In-memory mock of apollo config service and portal, for the tests without a real apollo.
+//!
+//! The [MockApollo] is a [HttpTransport], the clients created by it send the requests to the
+//! in-memory store instead of the network, and the tests can mutate the store to trigger the
+//! watching. For the clients created from a url, serve it on a local port by
+//! [MockApollo::serve].
+//!
+//! Supported apis:
+//!
+//! - Meta server: `/services/config`, see [MockApollo::set_config_services].
+//! - Config service: `/configs`, `/configfiles` and `/notifications/v2` with long polling.
+//! - Portal (feature `open`): env clusters, cluster, namespaces, get, list, create, update and
+//!   delete items, publish and rollback releases, latest release, release history, get
+//!   release, create apps, clusters and
+//!   namespaces, link namespaces.
+//!
+//! The config service and the methods of [MockApollo] serve the `DEV` env, the open apis serve
+//! the env of the request path, so the other envs can be filled by the open apis. The
+//! namespaces are created on demand.
+//!
+//! Gray release is not supported, the gray branch apis of portal respond `404`, and the
+//! `label` of the config service requests is ignored, so the main release is always served.
+//!
+//! # Example
+//!
+//! ```
+//! use apollo_client::{
+//!     conf::requests::FetchRequest,
+//!     testing::MockApollo,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let apollo = MockApollo::new();
+//!     apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+//!
+//!     let client = apollo.conf_client_builder().build().unwrap();
+//!     let response = client
+//!         .fetch(FetchRequest {
+//!             app_id: "SampleApp".to_string(),
+//!             namespace_name: "application".to_string(),
+//!             ..Default::default()
+//!         })
+//!         .await
+//!         .unwrap();
+//!     assert_eq!(response.configurations["timeout"], "100");
+//! }
+//! ```
+
+mod conf;
+#[cfg(feature = "open")]
+mod open;
+mod server;
+
+pub use server::MockApolloServer;
+
+use crate::{
+    conf::ApolloConfClientBuilder,
+    errors::ApolloClientResult,
+    transport::{HttpRequest, HttpResponse, HttpTransport},
+    utils::canonicalize_namespace,
+};
+use chrono::{DateTime, Local};
+use http::{Method, StatusCode};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::sync::watch;
+use url::Url;
+
+/// Base url of the mock config service and portal, the host is never resolved.
+pub const MOCK_APOLLO_URL: &str = "http://apollo.mock/";
+
+/// Env of the config service and the methods of [MockApollo].
+const MOCK_ENV: &str = "DEV";
+
+/// Default long poll timeout of the notifications, same as apollo.
+const DEFAULT_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// In-memory mock of apollo, cheap to clone, the clones share the same store.
+#[derive(Clone)]
+pub struct MockApollo {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    store: Mutex<Store>,
+    /// Version of the store, bumped when released, to wake the long polls.
+    version: watch::Sender<u64>,
+}
+
+impl Default for MockApollo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockApollo {
+    /// Create the mock with an empty store.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                store: Mutex::new(Store::new()),
+                version: watch::channel(0).0,
+            }),
+        }
+    }
+
+    /// Customize the time of the long poll waiting for the release before responding `304`,
+    /// default is 60 seconds.
+    pub fn set_long_poll_timeout(&self, timeout: Duration) {
+        self.store().long_poll_timeout = timeout;
+    }
+
+    /// Customize the config service instances discovered via the meta server, default is
+    /// [MOCK_APOLLO_URL].
+    pub fn set_config_services(&self, urls: impl IntoIterator<Item = Url>) {
+        self.store().config_services = urls.into_iter().collect();
+    }
+
+    /// Builder of the configuration client requesting this mock.
+    pub fn conf_client_builder(&self) -> ApolloConfClientBuilder {
+        ApolloConfClientBuilder::new_via_config_service(Url::parse(MOCK_APOLLO_URL).unwrap())
+            .unwrap()
+            .with_transport(self.clone())
+    }
+
+    /// Builder of the open api client requesting this mock, the token is not checked.
+    #[cfg(feature = "open")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "open")))]
+    pub fn open_client_builder(&self) -> crate::open::OpenApiClientBuilder {
+        crate::open::OpenApiClientBuilder::new(Url::parse(MOCK_APOLLO_URL).unwrap(), "mock-token")
+            .unwrap()
+            .with_transport(self.clone())
+    }
+
+    /// Replace the items of the namespace and release it, the watching clients are notified.
+    ///
+    /// Return the release key.
+    pub fn publish<K, V>(
+        &self,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        configurations: impl IntoIterator<Item = (K, V)>,
+    ) -> String
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let release_key = {
+            let mut store = self.store();
+            let namespace = store.namespace_mut(MOCK_ENV, app_id, cluster_name, namespace_name);
+            namespace.items = configurations
+                .into_iter()
+                .map(|(key, value)| Item::new(key.into(), value.into(), None, "apollo"))
+                .collect();
+            store.release(
+                MOCK_ENV,
+                app_id,
+                cluster_name,
+                namespace_name,
+                "",
+                None,
+                "apollo",
+            )
+        };
+        self.notify_released();
+        release_key
+    }
+
+    /// Set the item of the namespace without releasing.
+    pub fn set_item(
+        &self,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        key: &str,
+        value: &str,
+    ) {
+        self.store()
+            .namespace_mut(MOCK_ENV, app_id, cluster_name, namespace_name)
+            .set_item(key, value, None, "apollo");
+    }
+
+    /// Release the items of the namespace, the watching clients are notified.
+    ///
+    /// Return the release key.
+    pub fn release(&self, app_id: &str, cluster_name: &str, namespace_name: &str) -> String {
+        let release_key = self.store().release(
+            MOCK_ENV,
+            app_id,
+            cluster_name,
+            namespace_name,
+            "",
+            None,
+            "apollo",
+        );
+        self.notify_released();
+        release_key
+    }
+
+    /// The released configurations of the namespace.
+    pub fn configurations(
+        &self,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> Option<HashMap<String, String>> {
+        self.store()
+            .namespace(MOCK_ENV, app_id, cluster_name, namespace_name)?
+            .release()
+            .map(|release| release.configurations.clone())
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        self.inner.store.lock().unwrap()
+    }
+
+    fn notify_released(&self) {
+        self.inner.version.send_modify(|version| *version += 1);
+    }
+
+    async fn handle(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+        let route = Route::new(&request);
+        let segments = route
+            .segments
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        match (request.method(), segments.as_slice()) {
+            (&Method::GET, ["configs", app_id, cluster_name, namespace_name]) => {
+                self.handle_fetch(&route, app_id, cluster_name, namespace_name)
+            }
+            (&Method::GET, ["configfiles", app_id, cluster_name, namespace_name]) => {
+                self.handle_cached_fetch(app_id, cluster_name, namespace_name)
+            }
+            (&Method::GET, ["notifications", "v2"]) => self.handle_notify(&request, &route).await,
+            (&Method::GET, ["services", "config"]) => self.handle_config_services(),
+            #[cfg(feature = "open")]
+            (_, ["openapi", "v1", segments @ ..]) => self.handle_open(&request, &route, segments),
+            _ => text_response(StatusCode::NOT_FOUND, "Not Found"),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpTransport for MockApollo {
+    async fn send(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+        self.handle(request).await
+    }
+}
+
+/// Parsed path segments and queries of request.
+struct Route {
+    segments: Vec<String>,
+    queries: HashMap<String, String>,
+}
+
+impl Route {
+    fn new(request: &HttpRequest) -> Self {
+        let segments = request
+            .uri()
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect();
+        let queries = form_urlencoded::parse(request.uri().query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+        Self { segments, queries }
+    }
+
+    fn query(&self, name: &str) -> Option<&str> {
+        self.queries.get(name).map(String::as_str)
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn text_response(status: StatusCode, body: &str) -> ApolloClientResult<HttpResponse> {
+    Ok(http::Response::builder()
+        .status(status)
+        .body(body.as_bytes().to_vec())?)
+}
+
+fn json_response(
+    status: StatusCode,
+    body: &impl serde::Serialize,
+) -> ApolloClientResult<HttpResponse> {
+    Ok(http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(body)?)?)
+}
+
+/// Key of namespace, in the order of env, app id, cluster name and namespace name, the
+/// namespace name is canonicalized.
+type NamespaceKey = (String, String, String, String);
+
+struct Store {
+    namespaces: BTreeMap<NamespaceKey, Namespace>,
+    next_id: i32,
+    long_poll_timeout: Duration,
+    config_services: Vec<Url>,
+}
+
+impl Store {
+    fn new() -> Self {
+        Self {
+            namespaces: BTreeMap::new(),
+            next_id: 1,
+            long_poll_timeout: DEFAULT_LONG_POLL_TIMEOUT,
+            config_services: vec![Url::parse(MOCK_APOLLO_URL).unwrap()],
+        }
+    }
+
+    fn key(env: &str, app_id: &str, cluster_name: &str, namespace_name: &str) -> NamespaceKey {
+        (
+            env.to_string(),
+            app_id.to_string(),
+            cluster_name.to_string(),
+            canonicalize_namespace(namespace_name),
+        )
+    }
+
+    fn namespace(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> Option<&Namespace> {
+        self.namespaces
+            .get(&Self::key(env, app_id, cluster_name, namespace_name))
+    }
+
+    fn namespace_mut(
+        &mut self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> &mut Namespace {
+        self.namespaces
+            .entry(Self::key(env, app_id, cluster_name, namespace_name))
+            .or_default()
+    }
+
+    fn next_id(&mut self) -> i32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Release the items of namespace, return the release key.
+    #[allow(clippy::too_many_arguments)]
+    fn release(
+        &mut self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        name: &str,
+        comment: Option<String>,
+        released_by: &str,
+    ) -> String {
+        let id = self.next_id();
+        let release_key = format!("{}-{}", Local::now().format("%Y%m%d%H%M%S"), id);
+        let namespace = self.namespace_mut(env, app_id, cluster_name, namespace_name);
+        let configurations = namespace
+            .items
+            .iter()
+            .map(|item| (item.key.clone(), item.value.clone()))
+            .collect();
+        namespace.releases.push(Release {
+            id,
+            release_key: release_key.clone(),
+            name: name.to_string(),
+            comment,
+            configurations,
+            released_by: released_by.to_string(),
+            time: Local::now(),
+            abandoned: false,
+        });
+        namespace.notification_id = Some(id);
+        release_key
+    }
+
+    /// Find the release of the env by id, with the key of its namespace.
+    #[cfg(feature = "open")]
+    fn find_release(&self, env: &str, release_id: i32) -> Option<(&NamespaceKey, &Release)> {
+        self.namespaces
+            .iter()
+            .filter(|((key_env, ..), _)| key_env == env)
+            .find_map(|(key, namespace)| {
+                namespace
+                    .releases
+                    .iter()
+                    .find(|release| release.id == release_id)
+                    .map(|release| (key, release))
+            })
+    }
+
+    /// Abandon the active release of the env, the previous active release takes effect, same
+    /// as apollo.
+    #[cfg_attr(not(feature = "open"), allow(dead_code))]
+    fn rollback(&mut self, env: &str, release_id: i32) -> Result<(), (StatusCode, &'static str)> {
+        let notification_id = self.next_id();
+        let namespace = self
+            .namespaces
+            .iter_mut()
+            .filter(|((key_env, ..), _)| key_env == env)
+            .map(|(_, namespace)| namespace)
+            .find(|namespace| {
+                namespace
+                    .releases
+                    .iter()
+                    .any(|release| release.id == release_id)
+            })
+            .ok_or((StatusCode::NOT_FOUND, "release not found"))?;
+
+        if namespace
+            .releases
+            .iter()
+            .filter(|release| !release.abandoned)
+            .count()
+            < 2
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "can't rollback namespace, there is only one active release",
+            ));
+        }
+        let release = namespace
+            .releases
+            .iter_mut()
+            .find(|release| release.id == release_id)
+            .unwrap();
+        if release.abandoned {
+            return Err((StatusCode::BAD_REQUEST, "release is not active"));
+        }
+        release.abandoned = true;
+        namespace.notification_id = Some(notification_id);
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Namespace {
+    /// Items not released yet.
+    items: Vec<Item>,
+    /// Releases in the order of publishing, including the abandoned.
+    releases: Vec<Release>,
+    /// Id of the latest release message.
+    notification_id: Option<i32>,
+}
+
+impl Namespace {
+    /// Id of the latest release message, `-1` if never released.
+    fn notification_id(&self) -> i32 {
+        self.notification_id.unwrap_or(-1)
+    }
+
+    /// The active release.
+    fn release(&self) -> Option<&Release> {
+        self.releases
+            .iter()
+            .rev()
+            .find(|release| !release.abandoned)
+    }
+
+    #[cfg_attr(not(feature = "open"), allow(dead_code))]
+    fn item(&self, key: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.key == key)
+    }
+
+    fn set_item(&mut self, key: &str, value: &str, comment: Option<String>, operator: &str) {
+        match self.items.iter_mut().find(|item| item.key == key) {
+            Some(item) => {
+                item.value = value.to_string();
+                item.comment = comment;
+                item.modified_by = operator.to_string();
+                item.modified_time = Local::now();
+            }
+            None => self.items.push(Item::new(
+                key.to_string(),
+                value.to_string(),
+                comment,
+                operator,
+            )),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "open"), allow(dead_code))]
+struct Item {
+    key: String,
+    value: String,
+    comment: Option<String>,
+    created_by: String,
+    modified_by: String,
+    created_time: DateTime<Local>,
+    modified_time: DateTime<Local>,
+}
+
+impl Item {
+    fn new(key: String, value: String, comment: Option<String>, operator: &str) -> Self {
+        let now = Local::now();
+        Self {
+            key,
+            value,
+            comment,
+            created_by: operator.to_string(),
+            modified_by: operator.to_string(),
+            created_time: now,
+            modified_time: now,
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "open"), allow(dead_code))]
+struct Release {
+    id: i32,
+    release_key: String,
+    name: String,
+    comment: Option<String>,
+    configurations: HashMap<String, String>,
+    released_by: String,
+    time: DateTime<Local>,
+    abandoned: bool,
+}
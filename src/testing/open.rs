@@ -0,0 +1,748 @@
+//! Handlers of the mock portal open apis.
+
+use super::{json_response, Item, MockApollo, Namespace, Release, Route, Store, MOCK_ENV};
+use crate::{
+    errors::ApolloClientResult,
+    meta::DEFAULT_CLUSTER_NAME,
+    open::{
+        meta::{OpenCreatedApp, OpenCreatedItem, OpenRelease, OpenUpdateItem},
+        responses::{OpenEnvClusterResponse, OpenItemResponse, OpenPageResponse},
+    },
+    transport::{HttpRequest, HttpResponse},
+    utils::canonicalize_namespace,
+};
+use chrono::{DateTime, Local};
+use http::{Method, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+impl MockApollo {
+    pub(super) fn handle_open(
+        &self,
+        request: &HttpRequest,
+        route: &Route,
+        segments: &[&str],
+    ) -> ApolloClientResult<HttpResponse> {
+        match (request.method(), segments) {
+            (&Method::GET, ["apps", app_id, "envclusters"]) => self.handle_env_clusters(app_id),
+            (&Method::POST, ["apps"]) => match parse_body(request) {
+                Ok(app) => self.handle_create_app(app),
+                Err(response) => response,
+            },
+            (&Method::POST, ["envs", env, "apps", app_id, "clusters"]) => match parse_body(request)
+            {
+                Ok(cluster) => self.handle_create_cluster(env, app_id, cluster),
+                Err(response) => response,
+            },
+            (&Method::POST, ["apps", app_id, "appnamespaces"]) => match parse_body(request) {
+                Ok(namespace) => self.handle_create_namespace(app_id, namespace),
+                Err(response) => response,
+            },
+            (
+                &Method::POST,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces"],
+            ) => match parse_body(request) {
+                Ok(namespace) => self.handle_link_namespace(env, app_id, cluster_name, namespace),
+                Err(response) => response,
+            },
+            (&Method::GET, ["envs", env, "apps", app_id, "clusters", cluster_name]) => {
+                self.handle_cluster(env, app_id, cluster_name)
+            }
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces"],
+            ) => self.handle_namespaces(env, app_id, cluster_name),
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name],
+            ) => self.handle_get_namespace(env, app_id, cluster_name, namespace_name),
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "releases", "latest"],
+            ) => self.handle_latest_release(env, app_id, cluster_name, namespace_name),
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "releases"],
+            ) => self.handle_release_history(route, env, app_id, cluster_name, namespace_name),
+            (&Method::GET, ["envs", env, "releases", release_id]) => {
+                self.handle_get_release(env, release_id)
+            }
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "items"],
+            ) => self.handle_list_items(route, env, app_id, cluster_name, namespace_name),
+            (
+                &Method::GET,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "items", key],
+            ) => self.handle_get_item(env, app_id, cluster_name, namespace_name, key),
+            (
+                &Method::DELETE,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "items", key],
+            ) => self.handle_delete_item(env, app_id, cluster_name, namespace_name, key),
+            (
+                &Method::POST,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "items"],
+            ) => match parse_body(request) {
+                Ok(item) => {
+                    self.handle_create_item(env, app_id, cluster_name, namespace_name, item)
+                }
+                Err(response) => response,
+            },
+            (
+                &Method::PUT,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "items", key],
+            ) => match parse_body(request) {
+                Ok(item) => self.handle_update_item(
+                    env,
+                    app_id,
+                    cluster_name,
+                    namespace_name,
+                    key,
+                    route.query("createIfNotExists") == Some("true"),
+                    item,
+                ),
+                Err(response) => response,
+            },
+            (
+                &Method::POST,
+                ["envs", env, "apps", app_id, "clusters", cluster_name, "namespaces", namespace_name, "releases"],
+            ) => match parse_body(request) {
+                Ok(release) => {
+                    self.handle_publish(env, app_id, cluster_name, namespace_name, release)
+                }
+                Err(response) => response,
+            },
+            (&Method::PUT, ["envs", env, "releases", release_id, "rollback"]) => {
+                self.handle_rollback(env, release_id)
+            }
+            _ => error_response(StatusCode::NOT_FOUND, "Not Found"),
+        }
+    }
+
+    fn handle_env_clusters(&self, app_id: &str) -> ApolloClientResult<HttpResponse> {
+        let mut env_clusters = BTreeMap::<_, Vec<_>>::new();
+        for (env, cluster_name) in app_clusters(&self.store(), app_id) {
+            env_clusters.entry(env).or_default().push(cluster_name);
+        }
+        if env_clusters.is_empty() {
+            return error_response(StatusCode::NOT_FOUND, "app not found");
+        }
+
+        json_response(
+            StatusCode::OK,
+            &env_clusters
+                .into_iter()
+                .map(|(env, clusters)| OpenEnvClusterResponse { env, clusters })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Create the `application` namespace of the `default` cluster in the `DEV` env, same as
+    /// apollo with only one env.
+    fn handle_create_app(&self, app: CreatedApp) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        if !app_clusters(&store, &app.app.app_id).is_empty() {
+            return error_response(StatusCode::BAD_REQUEST, "app already exists");
+        }
+        store.namespace_mut(
+            MOCK_ENV,
+            &app.app.app_id,
+            DEFAULT_CLUSTER_NAME,
+            "application",
+        );
+        Ok(http::Response::builder()
+            .status(StatusCode::OK)
+            .body(Vec::new())?)
+    }
+
+    /// Create the namespaces of the `default` cluster in the new cluster.
+    fn handle_create_cluster(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster: CreatedCluster,
+    ) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        let clusters = app_clusters(&store, app_id)
+            .into_iter()
+            .filter(|(cluster_env, _)| cluster_env == env)
+            .map(|(_, cluster_name)| cluster_name)
+            .collect::<BTreeSet<_>>();
+        if clusters.is_empty() {
+            return error_response(StatusCode::NOT_FOUND, "app not found");
+        }
+        if clusters.contains(&cluster.name) {
+            return error_response(StatusCode::BAD_REQUEST, "cluster already exists");
+        }
+
+        let namespace_names = cluster_namespaces(&store, env, app_id, DEFAULT_CLUSTER_NAME)
+            .map(|(namespace_name, _)| namespace_name.to_string())
+            .collect::<Vec<_>>();
+        for namespace_name in namespace_names {
+            store.namespace_mut(env, app_id, &cluster.name, &namespace_name);
+        }
+
+        json_response(
+            StatusCode::OK,
+            &with_base_fields(
+                json!({
+                    "name": cluster.name,
+                    "appId": app_id,
+                }),
+                (&cluster.data_change_created_by, Local::now()),
+                (&cluster.data_change_created_by, Local::now()),
+            ),
+        )
+    }
+
+    /// Create the namespace in all envs and clusters of the app.
+    fn handle_create_namespace(
+        &self,
+        app_id: &str,
+        namespace: CreatedAppNamespace,
+    ) -> ApolloClientResult<HttpResponse> {
+        let namespace_name = match namespace.format.as_str() {
+            "properties" => namespace.name.clone(),
+            format => format!("{}.{}", namespace.name, format),
+        };
+
+        let mut store = self.store();
+        let clusters = app_clusters(&store, app_id);
+        if clusters.is_empty() {
+            return error_response(StatusCode::NOT_FOUND, "app not found");
+        }
+        if clusters.iter().any(|(env, cluster_name)| {
+            store
+                .namespace(env, app_id, cluster_name, &namespace_name)
+                .is_some()
+        }) {
+            return error_response(StatusCode::BAD_REQUEST, "namespace already exists");
+        }
+        for (env, cluster_name) in clusters {
+            store.namespace_mut(&env, app_id, &cluster_name, &namespace_name);
+        }
+
+        json_response(
+            StatusCode::OK,
+            &with_base_fields(
+                json!({
+                    "name": namespace.name,
+                    "appId": app_id,
+                    "format": namespace.format,
+                    "isPublic": namespace.is_public,
+                    "appendNamespacePrefix": namespace.append_namespace_prefix,
+                    "comment": namespace.comment,
+                }),
+                (&namespace.data_change_created_by, Local::now()),
+                (&namespace.data_change_created_by, Local::now()),
+            ),
+        )
+    }
+
+    fn handle_link_namespace(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace: LinkedNamespace,
+    ) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        if store
+            .namespace(env, app_id, cluster_name, &namespace.namespace_name)
+            .is_some()
+        {
+            return error_response(StatusCode::BAD_REQUEST, "namespace already exists");
+        }
+        let linked = store.namespace_mut(env, app_id, cluster_name, &namespace.namespace_name);
+        json_response(
+            StatusCode::OK,
+            &namespace_response(
+                app_id,
+                cluster_name,
+                &canonicalize_namespace(&namespace.namespace_name),
+                linked,
+            ),
+        )
+    }
+
+    fn handle_cluster(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        let mut namespaces = cluster_namespaces(&store, env, app_id, cluster_name).peekable();
+        if namespaces.peek().is_none() {
+            return error_response(StatusCode::NOT_FOUND, "cluster not found");
+        }
+
+        json_response(
+            StatusCode::OK,
+            &with_base_fields(
+                json!({
+                    "name": cluster_name,
+                    "appId": app_id,
+                }),
+                created(None),
+                modified(None),
+            ),
+        )
+    }
+
+    fn handle_namespaces(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        let namespaces = cluster_namespaces(&store, env, app_id, cluster_name)
+            .map(|(namespace_name, namespace)| {
+                namespace_response(app_id, cluster_name, namespace_name, namespace)
+            })
+            .collect::<Vec<_>>();
+        json_response(StatusCode::OK, &namespaces)
+    }
+
+    fn handle_get_namespace(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        match store.namespace(env, app_id, cluster_name, namespace_name) {
+            Some(namespace) => json_response(
+                StatusCode::OK,
+                &namespace_response(
+                    app_id,
+                    cluster_name,
+                    &canonicalize_namespace(namespace_name),
+                    namespace,
+                ),
+            ),
+            None => error_response(StatusCode::NOT_FOUND, "namespace not found"),
+        }
+    }
+
+    /// Respond empty body if never released, same as apollo.
+    fn handle_latest_release(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        let namespace = match store.namespace(env, app_id, cluster_name, namespace_name) {
+            Some(namespace) => namespace,
+            None => return error_response(StatusCode::NOT_FOUND, "namespace not found"),
+        };
+        match namespace.release() {
+            Some(release) => json_response(
+                StatusCode::OK,
+                &release_response(app_id, cluster_name, namespace_name, release),
+            ),
+            None => Ok(http::Response::builder()
+                .status(StatusCode::OK)
+                .body(Vec::new())?),
+        }
+    }
+
+    /// Respond all the releases including the abandoned, the latest first.
+    fn handle_release_history(
+        &self,
+        route: &Route,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let (page, size) = page_and_size(route);
+        let store = self.store();
+        let releases = match store.namespace(env, app_id, cluster_name, namespace_name) {
+            Some(namespace) => &namespace.releases,
+            None => return error_response(StatusCode::NOT_FOUND, "namespace not found"),
+        };
+        json_response(
+            StatusCode::OK,
+            &OpenPageResponse {
+                page,
+                size,
+                total: releases.len() as u64,
+                content: releases
+                    .iter()
+                    .rev()
+                    .skip((page * size) as usize)
+                    .take(size as usize)
+                    .map(|release| release_response(app_id, cluster_name, namespace_name, release))
+                    .collect(),
+            },
+        )
+    }
+
+    fn handle_get_release(&self, env: &str, release_id: &str) -> ApolloClientResult<HttpResponse> {
+        let release_id = match release_id.parse() {
+            Ok(release_id) => release_id,
+            Err(_) => return error_response(StatusCode::BAD_REQUEST, "invalid release id"),
+        };
+        let store = self.store();
+        match store.find_release(env, release_id) {
+            Some(((_, app_id, cluster_name, namespace_name), release)) => json_response(
+                StatusCode::OK,
+                &release_response(app_id, cluster_name, namespace_name, release),
+            ),
+            None => error_response(StatusCode::NOT_FOUND, "release not found"),
+        }
+    }
+
+    fn handle_list_items(
+        &self,
+        route: &Route,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let (page, size) = page_and_size(route);
+        let store = self.store();
+        let items = match store.namespace(env, app_id, cluster_name, namespace_name) {
+            Some(namespace) => &namespace.items,
+            None => return error_response(StatusCode::NOT_FOUND, "namespace not found"),
+        };
+        json_response(
+            StatusCode::OK,
+            &OpenPageResponse {
+                page,
+                size,
+                total: items.len() as u64,
+                content: items
+                    .iter()
+                    .skip((page * size) as usize)
+                    .take(size as usize)
+                    .map(item_response)
+                    .collect(),
+            },
+        )
+    }
+
+    fn handle_get_item(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        key: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let store = self.store();
+        match store
+            .namespace(env, app_id, cluster_name, namespace_name)
+            .and_then(|namespace| namespace.item(key))
+        {
+            Some(item) => json_response(StatusCode::OK, &item_response(item)),
+            None => error_response(StatusCode::NOT_FOUND, "item not found"),
+        }
+    }
+
+    fn handle_delete_item(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        key: &str,
+    ) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        let namespace = store.namespace_mut(env, app_id, cluster_name, namespace_name);
+        let len = namespace.items.len();
+        namespace.items.retain(|item| item.key != key);
+        if namespace.items.len() == len {
+            return error_response(StatusCode::NOT_FOUND, "item not found");
+        }
+        Ok(http::Response::builder()
+            .status(StatusCode::OK)
+            .body(Vec::new())?)
+    }
+
+    fn handle_create_item(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        item: OpenCreatedItem,
+    ) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        let namespace = store.namespace_mut(env, app_id, cluster_name, namespace_name);
+        if namespace.item(&item.key).is_some() {
+            return error_response(StatusCode::BAD_REQUEST, "item already exists");
+        }
+
+        namespace.set_item(
+            &item.key,
+            &item.value,
+            item.comment,
+            &item.data_change_created_by,
+        );
+        json_response(
+            StatusCode::OK,
+            &item_response(namespace.item(&item.key).unwrap()),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn handle_update_item(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        key: &str,
+        create_if_not_exists: bool,
+        item: OpenUpdateItem,
+    ) -> ApolloClientResult<HttpResponse> {
+        let mut store = self.store();
+        let namespace = store.namespace_mut(env, app_id, cluster_name, namespace_name);
+        if namespace.item(key).is_none() && !create_if_not_exists {
+            return error_response(StatusCode::NOT_FOUND, "item not found");
+        }
+
+        namespace.set_item(
+            key,
+            &item.value,
+            item.comment,
+            &item.data_change_last_modified_by,
+        );
+        Ok(http::Response::builder()
+            .status(StatusCode::OK)
+            .body(Vec::new())?)
+    }
+
+    fn handle_publish(
+        &self,
+        env: &str,
+        app_id: &str,
+        cluster_name: &str,
+        namespace_name: &str,
+        release: OpenRelease,
+    ) -> ApolloClientResult<HttpResponse> {
+        let response = {
+            let mut store = self.store();
+            store.release(
+                env,
+                app_id,
+                cluster_name,
+                namespace_name,
+                &release.release_title,
+                release.release_comment,
+                &release.released_by,
+            );
+            let release = store
+                .namespace(env, app_id, cluster_name, namespace_name)
+                .and_then(Namespace::release)
+                .unwrap();
+            release_response(app_id, cluster_name, namespace_name, release)
+        };
+        self.notify_released();
+        json_response(StatusCode::OK, &response)
+    }
+
+    fn handle_rollback(&self, env: &str, release_id: &str) -> ApolloClientResult<HttpResponse> {
+        let release_id = match release_id.parse() {
+            Ok(release_id) => release_id,
+            Err(_) => return error_response(StatusCode::BAD_REQUEST, "invalid release id"),
+        };
+        if let Err((status, message)) = self.store().rollback(env, release_id) {
+            return error_response(status, message);
+        }
+        self.notify_released();
+        Ok(http::Response::builder()
+            .status(StatusCode::OK)
+            .body(Vec::new())?)
+    }
+}
+
+/// The page and size queries of the paginated requests.
+fn page_and_size(route: &Route) -> (u32, u32) {
+    let page = route
+        .query("page")
+        .and_then(|page| page.parse().ok())
+        .unwrap_or(0);
+    let size = route
+        .query("size")
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(50);
+    (page, size)
+}
+
+/// Parse the json body, or the bad request response.
+#[allow(clippy::result_large_err)]
+fn parse_body<T: DeserializeOwned>(
+    request: &HttpRequest,
+) -> Result<T, ApolloClientResult<HttpResponse>> {
+    serde_json::from_slice(request.body())
+        .map_err(|e| error_response(StatusCode::BAD_REQUEST, &e.to_string()))
+}
+
+fn error_response(status: StatusCode, message: &str) -> ApolloClientResult<HttpResponse> {
+    json_response(
+        status,
+        &json!({
+            "status": status.as_u16(),
+            "message": message,
+        }),
+    )
+}
+
+#[derive(Deserialize)]
+struct CreatedApp {
+    app: OpenCreatedApp,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedCluster {
+    name: String,
+    data_change_created_by: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedAppNamespace {
+    name: String,
+    format: String,
+    is_public: bool,
+    append_namespace_prefix: bool,
+    comment: Option<String>,
+    data_change_created_by: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkedNamespace {
+    namespace_name: String,
+}
+
+/// Envs and clusters of the app, empty if the app not exists.
+fn app_clusters(store: &Store, app_id: &str) -> BTreeSet<(String, String)> {
+    store
+        .namespaces
+        .keys()
+        .filter(|(_, key_app_id, ..)| key_app_id == app_id)
+        .map(|(env, _, cluster_name, _)| (env.clone(), cluster_name.clone()))
+        .collect()
+}
+
+fn cluster_namespaces<'a>(
+    store: &'a Store,
+    env: &'a str,
+    app_id: &'a str,
+    cluster_name: &'a str,
+) -> impl Iterator<Item = (&'a str, &'a Namespace)> {
+    store
+        .namespaces
+        .iter()
+        .filter(move |((key_env, key_app_id, key_cluster_name, _), _)| {
+            key_env == env && key_app_id == app_id && key_cluster_name == cluster_name
+        })
+        .map(|((.., namespace_name), namespace)| (namespace_name.as_str(), namespace))
+}
+
+/// Split the canonical namespace name into the apollo namespace name and format, the
+/// `.properties` suffix is omitted by apollo.
+fn split_format(namespace_name: &str) -> (&str, &str) {
+    match namespace_name.rsplit_once('.') {
+        Some((name, "properties")) => (name, "properties"),
+        Some((_, format)) => (namespace_name, format),
+        None => (namespace_name, "properties"),
+    }
+}
+
+/// Response of the namespace, the namespace name is canonical.
+fn namespace_response(
+    app_id: &str,
+    cluster_name: &str,
+    namespace_name: &str,
+    namespace: &Namespace,
+) -> Value {
+    let (namespace_name, format) = split_format(namespace_name);
+    with_base_fields(
+        json!({
+            "appId": app_id,
+            "clusterName": cluster_name,
+            "namespaceName": namespace_name,
+            "comment": null,
+            "format": format,
+            "isPublic": false,
+            "items": namespace.items.iter().map(item_response).collect::<Vec<_>>(),
+        }),
+        created(namespace.items.first()),
+        modified(namespace.items.first()),
+    )
+}
+
+fn release_response(
+    app_id: &str,
+    cluster_name: &str,
+    namespace_name: &str,
+    release: &Release,
+) -> Value {
+    with_base_fields(
+        json!({
+            "appId": app_id,
+            "clusterName": cluster_name,
+            "namespaceName": split_format(&canonicalize_namespace(namespace_name)).0,
+            "id": release.id,
+            "name": release.name,
+            "configurations": release.configurations,
+            "comment": release.comment,
+            "isAbandoned": release.abandoned,
+        }),
+        (&release.released_by, release.time),
+        (&release.released_by, release.time),
+    )
+}
+
+fn item_response(item: &Item) -> OpenItemResponse {
+    OpenItemResponse {
+        key: item.key.clone(),
+        value: item.value.clone(),
+        comment: item.comment.clone(),
+        data_change_created_by: item.created_by.clone(),
+        data_change_last_modified_by: item.modified_by.clone(),
+        data_change_created_by_display_name: None,
+        data_change_last_modified_by_display_name: None,
+        data_change_created_time: item.created_time,
+        data_change_last_modified_time: item.modified_time,
+    }
+}
+
+/// Operator and time of the creation of item, or the default.
+fn created(item: Option<&Item>) -> (&str, DateTime<Local>) {
+    item.map(|item| (item.created_by.as_str(), item.created_time))
+        .unwrap_or_else(|| ("apollo", Local::now()))
+}
+
+/// Operator and time of the last modification of item, or the default.
+fn modified(item: Option<&Item>) -> (&str, DateTime<Local>) {
+    item.map(|item| (item.modified_by.as_str(), item.modified_time))
+        .unwrap_or_else(|| ("apollo", Local::now()))
+}
+
+/// Add the base fields of the open api responses.
+fn with_base_fields(
+    mut value: Value,
+    (created_by, created_time): (&str, DateTime<Local>),
+    (modified_by, modified_time): (&str, DateTime<Local>),
+) -> Value {
+    value["dataChangeCreatedBy"] = json!(created_by);
+    value["dataChangeLastModifiedBy"] = json!(modified_by);
+    value["dataChangeCreatedByDisplayName"] = Value::Null;
+    value["dataChangeLastModifiedByDisplayName"] = Value::Null;
+    value["dataChangeCreatedTime"] = json!(created_time);
+    value["dataChangeLastModifiedTime"] = json!(modified_time);
+    value
+}
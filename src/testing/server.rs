@@ -0,0 +1,164 @@
+//! Local http server of the mock, for the clients created from a url.
+
+use super::MockApollo;
+use crate::{
+    errors::ApolloClientResult,
+    transport::{HttpRequest, HttpResponse},
+};
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use url::Url;
+
+/// The mock served on a local port, started by [MockApollo::serve], stopped when dropped.
+pub struct MockApolloServer {
+    url: Url,
+    task: JoinHandle<()>,
+}
+
+impl MockApolloServer {
+    /// Base url of the server, such as `http://127.0.0.1:12345/`.
+    pub fn url(&self) -> Url {
+        self.url.clone()
+    }
+}
+
+impl Drop for MockApolloServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl MockApollo {
+    /// Serve the mock on a random local port, so that the clients created from a url, such as
+    /// by [crate::conf::ApolloConfClientBuilder::new_via_config_service], can request it. The
+    /// server shares the store with this mock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_client::{
+    ///     conf::{requests::FetchRequest, ApolloConfClientBuilder},
+    ///     testing::MockApollo,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let apollo = MockApollo::new();
+    ///     apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    ///     let server = apollo.serve().await.unwrap();
+    ///
+    ///     let client = ApolloConfClientBuilder::new_via_config_service(server.url())
+    ///         .unwrap()
+    ///         .build()
+    ///         .unwrap();
+    ///     let response = client
+    ///         .fetch(FetchRequest {
+    ///             app_id: "SampleApp".to_string(),
+    ///             namespace_name: "application".to_string(),
+    ///             ..Default::default()
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(response.configurations["timeout"], "100");
+    /// }
+    /// ```
+    pub async fn serve(&self) -> ApolloClientResult<MockApolloServer> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+        let url = Url::parse(&format!("http://{}/", listener.local_addr()?))?;
+
+        let apollo = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        log::warn!("mock apollo accept failed: {}", e);
+                        continue;
+                    }
+                };
+                let apollo = apollo.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = apollo.serve_connection(stream).await {
+                        log::debug!("mock apollo connection closed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(MockApolloServer { url, task })
+    }
+
+    /// Serve the keep-alive requests of the connection, until closed by the client.
+    async fn serve_connection(&self, stream: TcpStream) -> ApolloClientResult<()> {
+        let mut stream = BufReader::new(stream);
+        while let Some(request) = read_request(&mut stream).await? {
+            let response = self.handle(request).await?;
+            write_response(stream.get_mut(), response).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the http/1.1 request, return `None` if the connection is closed.
+async fn read_request(
+    stream: &mut BufReader<TcpStream>,
+) -> ApolloClientResult<Option<HttpRequest>> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let mut builder = http::Request::builder()
+        .method(parts.next().unwrap_or_default())
+        .uri(parts.next().unwrap_or_default());
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        stream.read_line(&mut line).await?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or_default();
+            }
+            builder = builder.header(name, value);
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    stream.read_exact(&mut body).await?;
+    Ok(Some(builder.body(body)?))
+}
+
+async fn write_response(stream: &mut TcpStream, response: HttpResponse) -> ApolloClientResult<()> {
+    let status = response.status();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_str(),
+        status.canonical_reason().unwrap_or_default()
+    );
+    for (name, value) in response.headers() {
+        head.push_str(&format!(
+            "{}: {}\r\n",
+            name,
+            String::from_utf8_lossy(value.as_bytes())
+        ));
+    }
+    head.push_str(&format!(
+        "content-length: {}\r\n\r\n",
+        response.body().len()
+    ));
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body()).await?;
+    stream.flush().await?;
+    Ok(())
+}
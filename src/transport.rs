@@ -0,0 +1,168 @@
+//! Pluggable http transport, the clients send requests through [HttpTransport].
+//!
+//! The default transport is [ReqwestTransport], enabled by feature `reqwest`, customize it by
+//! `with_transport` of the client builders, such as running over an existing hyper or tower
+//! stack, or an in-memory fake for tests.
+//!
+//! # Example
+//!
+//! ```
+//! use apollo_client::{
+//!     conf::ApolloConfClientBuilder,
+//!     errors::ApolloClientResult,
+//!     transport::{HttpRequest, HttpResponse, HttpTransport},
+//! };
+//! use url::Url;
+//!
+//! struct NotFoundTransport;
+//!
+//! #[async_trait::async_trait]
+//! impl HttpTransport for NotFoundTransport {
+//!     async fn send(&self, _request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+//!         Ok(http::Response::builder().status(404).body(Vec::new())?)
+//!     }
+//! }
+//!
+//! let _client = ApolloConfClientBuilder::new_via_config_service(
+//!     Url::parse("http://localhost:8080").unwrap(),
+//! )
+//! .unwrap()
+//! .with_transport(NotFoundTransport)
+//! .build()
+//! .unwrap();
+//! ```
+
+use crate::errors::ApolloClientResult;
+use std::time::Duration;
+
+/// Http request sent by the transport, with the full url, headers and body.
+pub type HttpRequest = http::Request<Vec<u8>>;
+
+/// Http response returned by the transport, with the full body.
+pub type HttpResponse = http::Response<Vec<u8>>;
+
+/// Timeout of the request, set in the extensions of [HttpRequest] for the requests which need
+/// a timeout other than the transport default, such as the long polls, the transport should
+/// respect it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout(pub Duration);
+
+/// Http transport to send the requests.
+///
+/// The errors occurred before receiving the response, such as connection errors and timeouts,
+/// should be returned as [crate::errors::ApolloClientError::Transport], they are treated as
+/// apollo unavailable. The non-success responses should be returned as it is.
+#[async_trait::async_trait]
+pub trait HttpTransport: Send + Sync + 'static {
+    /// Send the request, and return the response.
+    async fn send(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse>;
+}
+
+/// Transport by [reqwest::Client].
+#[cfg(feature = "reqwest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// Create the transport with the client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[async_trait::async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+        let timeout = request.extensions().get::<Timeout>().copied();
+        let mut request = reqwest::Request::try_from(request)?;
+        if let Some(Timeout(timeout)) = timeout {
+            *request.timeout_mut() = Some(timeout);
+        }
+
+        let response = self.client.execute(request).await?;
+
+        let mut builder = http::Response::builder()
+            .status(response.status())
+            .version(response.version());
+        if let Some(headers) = builder.headers_mut() {
+            *headers = response.headers().clone();
+        }
+        Ok(builder.body(response.bytes().await?.to_vec())?)
+    }
+}
+
+#[cfg(all(test, feature = "conf"))]
+mod tests {
+    use super::*;
+    use crate::conf::{
+        requests::{FetchRequest, NotifyRequest},
+        ApolloConfClientBuilder,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct FakeTransport {
+        requests: Arc<Mutex<Vec<HttpRequest>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpTransport for FakeTransport {
+        async fn send(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+            let body = if request.uri().path().starts_with("/configs/") {
+                r#"{"appId":"SampleApp","cluster":"default","namespaceName":"application","configurations":{"timeout":"100"},"releaseKey":"1"}"#
+            } else {
+                "[]"
+            };
+            self.requests.lock().unwrap().push(request);
+            Ok(http::Response::builder().body(body.as_bytes().to_vec())?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() {
+        let transport = FakeTransport::default();
+        let client = ApolloConfClientBuilder::new_via_config_service(
+            "http://localhost:8080".parse().unwrap(),
+        )
+        .unwrap()
+        .with_transport(transport.clone())
+        .build()
+        .unwrap();
+
+        let response = client
+            .fetch(FetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                label: Some("gray".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.configurations["timeout"], "100");
+
+        client
+            .notify(NotifyRequest {
+                app_id: "SampleApp".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(
+            requests[0].uri(),
+            "http://localhost:8080/configs/SampleApp/default/application?label=gray"
+        );
+        assert_eq!(requests[0].method(), http::Method::GET);
+        assert_eq!(requests[0].extensions().get::<Timeout>(), None);
+        assert_eq!(
+            requests[1].extensions().get::<Timeout>(),
+            Some(&Timeout(Duration::from_secs(90)))
+        );
+    }
+}
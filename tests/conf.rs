@@ -2,16 +2,17 @@ mod common;
 
 use apollo_client::{
     conf::{
+        config::ApolloConfig,
         meta::IpValue,
         requests::{CachedFetchRequest, FetchRequest, WatchRequest},
         ApolloConfClient, ApolloConfClientBuilder,
     },
     errors::ApolloClientError,
+    properties::Properties,
 };
 use common::{ensure_timeout, setup};
 use futures_util::{pin_mut, stream::StreamExt};
 use http::status::StatusCode;
-use ini::Properties;
 use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -172,6 +173,27 @@ async fn test_fetch_request() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_via_meta_server() {
+    setup();
+
+    let client = new_client_via_meta_server();
+
+    {
+        let response = client
+            .fetch(FetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application.properties".to_string(),
+                ip: Some(IpValue::HostName),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.app_id, "SampleApp");
+        assert_eq!(response.configurations["timeout"], "100");
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_watch_first() {
     setup();
@@ -253,6 +275,33 @@ async fn test_watch_first() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_apollo_config() {
+    setup();
+    ensure_timeout(Duration::from_secs(10));
+
+    let config = ApolloConfig::new(
+        new_client_via_config_service(),
+        WatchRequest {
+            app_id: "TestApp1".to_string(),
+            namespace_names: vec!["foo1".into(), "not_exists_namespace".into()],
+            ip: Some(IpValue::HostName),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(config.get("foo1", "foo1").as_deref(), Some("bar1"));
+    assert_eq!(
+        config.get("foo1.properties", "foo1").as_deref(),
+        Some("bar1")
+    );
+    assert_eq!(config.get("foo1", "not_exists_key"), None);
+    assert!(config.release_key("foo1").is_some());
+    assert_eq!(config.configurations("not_exists_namespace"), None);
+    assert_eq!(config.namespace_names(), ["foo1.properties"]);
+}
+
 #[cfg(feature = "open")]
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_watch_changed() {
@@ -473,9 +522,65 @@ async fn test_watch_changed() {
     handle.await.unwrap();
 }
 
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_client() {
+    use apollo_client::conf::blocking::BlockingApolloConfClient;
+
+    setup();
+
+    let client = BlockingApolloConfClient::new(new_client_via_config_service()).unwrap();
+
+    let properties = client
+        .cached_fetch(CachedFetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ip: Some(IpValue::HostName),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(properties.get("timeout"), Some("100"));
+
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ip: Some(IpValue::HostName),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(response.configurations["timeout"], "100");
+
+    let mut watch = client.watch(WatchRequest {
+        app_id: "TestApp1".to_string(),
+        namespace_names: vec!["foo1".into()],
+        ip: Some(IpValue::HostName),
+        ..Default::default()
+    });
+    let responses = watch.next().unwrap().unwrap();
+    assert_eq!(
+        responses["foo1"].as_ref().unwrap().configurations["foo1"],
+        "bar1"
+    );
+
+    let handle = watch.handle().clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(1));
+        handle.stop();
+    });
+    assert!(watch.next().is_none());
+}
+
 fn new_client_via_config_service() -> ApolloConfClient {
     ApolloConfClientBuilder::new_via_config_service("http://localhost:8080".parse().unwrap())
         .unwrap()
         .build()
         .unwrap()
 }
+
+fn new_client_via_meta_server() -> ApolloConfClient {
+    ApolloConfClientBuilder::new_via_meta_server("http://localhost:8080".parse().unwrap())
+        .unwrap()
+        .build()
+        .unwrap()
+}
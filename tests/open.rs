@@ -1,10 +1,16 @@
 use apollo_client::{
     errors::ApolloClientError,
+    meta::NamespaceKind,
     open::{
-        meta::{OpenCreatedItem, OpenRelease},
+        meta::{OpenCreatedItem, OpenGrayReleaseRuleItem, OpenRelease},
         requests::{
-            OpenAppRequest, OpenClusterRequest, OpenCreateItemRequest, OpenEnvClusterRequest,
-            OpenNamespaceRequest, OpenPublishNamespaceRequest,
+            OpenAppRequest, OpenClusterRequest, OpenCreateBranchRequest, OpenCreateClusterRequest,
+            OpenCreateItemRequest, OpenCreateNamespaceRequest, OpenDeleteBranchRequest,
+            OpenDeleteItemRequest, OpenEnvClusterRequest, OpenGetBranchRequest,
+            OpenGetGrayRulesRequest, OpenGetItemRequest, OpenGetNamespaceRequest,
+            OpenGrayReleaseRequest, OpenLatestReleaseRequest, OpenListItemsRequest,
+            OpenMergeBranchRequest, OpenNamespaceRequest, OpenPublishNamespaceRequest,
+            OpenRollbackReleaseRequest, OpenUpdateGrayRulesRequest,
         },
     },
 };
@@ -120,6 +126,43 @@ async fn test_namespace_request() {
             .await
             .unwrap();
     }
+
+    {
+        let response = client
+            .get_namespace(OpenGetNamespaceRequest {
+                env: "DEV".to_string(),
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.app_id, "SampleApp");
+        assert_eq!(response.namespace_name, "application");
+        assert_eq!(response.format, "properties");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_latest_release_request() {
+    setup();
+
+    let client = common::create_open_client();
+
+    {
+        let response = client
+            .latest_release(OpenLatestReleaseRequest {
+                env: "DEV".to_string(),
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.namespace_name, "application");
+        assert_eq!(response.configurations["timeout"], "100");
+    }
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -196,6 +239,73 @@ async fn test_curd_item_request() {
         ));
     }
 
+    {
+        let response = client
+            .get_item(OpenGetItemRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                key: "timeout".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.key, "timeout");
+        assert_eq!(response.value, "3000");
+    }
+
+    {
+        let response = client
+            .list_items(OpenListItemsRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.page, 0);
+        assert_eq!(response.total, 2);
+        let mut keys = response
+            .content
+            .iter()
+            .map(|item| item.key.as_str())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, ["connect_timeout", "timeout"]);
+    }
+
+    {
+        client
+            .delete_item(OpenDeleteItemRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                key: "connect_timeout".to_string(),
+                operator: "apollo".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .get_item(OpenGetItemRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                key: "connect_timeout".to_string(),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(
+            response,
+            Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::NOT_FOUND
+        ));
+    }
+
     {
         let response = client
             .publish_namespace(OpenPublishNamespaceRequest {
@@ -212,9 +322,359 @@ async fn test_curd_item_request() {
             .await
             .unwrap();
 
+        assert!(response.id.unwrap() > 0);
         assert_eq!(response.app_id, "TestApp2");
         assert_eq!(response.cluster_name, "default");
         assert_eq!(response.namespace_name, "application");
         assert_eq!(response.data_change_created_by, "apollo");
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_gray_branch_request() {
+    setup();
+
+    let client = common::create_open_client();
+
+    let branch = client
+        .create_branch(OpenCreateBranchRequest {
+            env: "DEV".to_string(),
+            app_id: "TestApp1".to_string(),
+            namespace_name: "application".to_string(),
+            operator: "apollo".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(branch.app_id, "TestApp1");
+    assert_eq!(branch.namespace_name, "application");
+    let branch_name = branch.cluster_name;
+
+    {
+        let response = client
+            .branch(OpenGetBranchRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.cluster_name, branch_name);
+    }
+
+    {
+        let rule_items = vec![OpenGrayReleaseRuleItem {
+            client_app_id: "TestApp1".to_string(),
+            client_ip_list: vec!["10.0.0.1".to_string()],
+            client_label_list: vec!["gray".to_string()],
+        }];
+        client
+            .update_gray_rules(OpenUpdateGrayRulesRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                branch_name: branch_name.clone(),
+                rule_items: rule_items.clone(),
+                operator: "apollo".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .gray_rules(OpenGetGrayRulesRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                branch_name: branch_name.clone(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.branch_name, branch_name);
+        assert_eq!(response.rule_items, rule_items);
+    }
+
+    {
+        client
+            .create_item(OpenCreateItemRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: branch_name.clone(),
+                item: OpenCreatedItem {
+                    key: "gray_timeout".to_string(),
+                    value: "100".to_string(),
+                    comment: None,
+                    data_change_created_by: "apollo".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .publish_gray_release(OpenGrayReleaseRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                branch_name: branch_name.clone(),
+                release: OpenRelease {
+                    release_title: "gray-release".to_string(),
+                    release_comment: None,
+                    released_by: "apollo".to_string(),
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.configurations["gray_timeout"], "100");
+    }
+
+    {
+        client
+            .delete_branch(OpenDeleteBranchRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                branch_name,
+                operator: "apollo".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .branch(OpenGetBranchRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(response.is_none());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_rollback_release_request() {
+    setup();
+
+    let client = common::create_open_client();
+
+    // Use a new cluster, so the releases don't disturb the other tests.
+    client
+        .create_cluster(OpenCreateClusterRequest {
+            env: "DEV".to_string(),
+            app_id: "TestApp2".to_string(),
+            cluster_name: "rollback".to_string(),
+            data_change_created_by: "apollo".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let mut releases = Vec::new();
+    for key in ["timeout", "connect_timeout"] {
+        client
+            .create_item(OpenCreateItemRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "rollback".to_string(),
+                item: OpenCreatedItem {
+                    key: key.to_string(),
+                    value: "100".to_string(),
+                    comment: None,
+                    data_change_created_by: "apollo".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .publish_namespace(OpenPublishNamespaceRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "rollback".to_string(),
+                release: OpenRelease {
+                    release_title: format!("{}-release", key),
+                    release_comment: None,
+                    released_by: "apollo".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+        releases.push(response);
+    }
+
+    {
+        client
+            .rollback_release(OpenRollbackReleaseRequest {
+                env: "DEV".to_string(),
+                release_id: releases[1].id.unwrap(),
+                operator: "apollo".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let response = client
+            .latest_release(OpenLatestReleaseRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp2".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "rollback".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.id, releases[0].id);
+        assert_eq!(response.configurations["timeout"], "100");
+        assert!(!response.configurations.contains_key("connect_timeout"));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_merge_branch_request() {
+    setup();
+
+    let client = common::create_open_client();
+
+    // Use a new cluster, so the branch doesn't conflict with `test_gray_branch_request`.
+    client
+        .create_cluster(OpenCreateClusterRequest {
+            env: "DEV".to_string(),
+            app_id: "TestApp1".to_string(),
+            cluster_name: "merge".to_string(),
+            data_change_created_by: "apollo".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let branch_name = client
+        .create_branch(OpenCreateBranchRequest {
+            env: "DEV".to_string(),
+            app_id: "TestApp1".to_string(),
+            namespace_name: "application".to_string(),
+            cluster_name: "merge".to_string(),
+            operator: "apollo".to_string(),
+        })
+        .await
+        .unwrap()
+        .cluster_name;
+
+    client
+        .create_item(OpenCreateItemRequest {
+            env: "DEV".to_string(),
+            app_id: "TestApp1".to_string(),
+            namespace_name: "application".to_string(),
+            cluster_name: branch_name.clone(),
+            item: OpenCreatedItem {
+                key: "gray_timeout".to_string(),
+                value: "100".to_string(),
+                comment: None,
+                data_change_created_by: "apollo".to_string(),
+            },
+        })
+        .await
+        .unwrap();
+
+    {
+        let response = client
+            .merge_branch(OpenMergeBranchRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "merge".to_string(),
+                branch_name,
+                delete_branch: true,
+                release: OpenRelease {
+                    release_title: "merge-release".to_string(),
+                    release_comment: None,
+                    released_by: "apollo".to_string(),
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.cluster_name, "merge");
+        assert_eq!(response.configurations["gray_timeout"], "100");
+    }
+
+    {
+        let response = client
+            .branch(OpenGetBranchRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "merge".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(response.is_none());
+
+        let response = client
+            .latest_release(OpenLatestReleaseRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                namespace_name: "application".to_string(),
+                cluster_name: "merge".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.configurations["gray_timeout"], "100");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_cluster_and_namespace_request() {
+    setup();
+
+    let client = common::create_open_client();
+
+    {
+        let response = client
+            .create_cluster(OpenCreateClusterRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                cluster_name: "created".to_string(),
+                data_change_created_by: "apollo".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.name, "created");
+        assert_eq!(response.app_id, "TestApp1");
+
+        let response = client
+            .cluster(OpenClusterRequest {
+                env: "DEV".to_string(),
+                app_id: "TestApp1".to_string(),
+                cluster_name: "created".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.name, "created");
+    }
+
+    {
+        let response = client
+            .create_namespace(OpenCreateNamespaceRequest {
+                app_id: "TestApp1".to_string(),
+                namespace_name: "created".to_string(),
+                format: NamespaceKind::Json,
+                is_public: false,
+                append_namespace_prefix: false,
+                comment: Some("created namespace".to_string()),
+                data_change_created_by: "apollo".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.name, "created");
+        assert_eq!(response.app_id, "TestApp1");
+        assert_eq!(response.format, "json");
+        assert!(!response.is_public);
+        assert_eq!(response.comment, Some("created namespace".to_string()));
+    }
+}
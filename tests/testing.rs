@@ -0,0 +1,1667 @@
+use apollo_client::{
+    conf::{
+        changes::ChangeType,
+        config::ApolloConfig,
+        meta::RetryPolicy,
+        requests::{CachedFetchRequest, FetchRequest, WatchRequest},
+        ApolloConfClientBuilder,
+    },
+    errors::{ApolloClientError, ApolloClientResult},
+    testing::{MockApollo, MOCK_APOLLO_URL},
+    transport::{HttpRequest, HttpResponse, HttpTransport},
+};
+use futures_util::StreamExt;
+use http::StatusCode;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+};
+use url::Url;
+
+/// Transport failing all the requests, as if apollo is unreachable.
+struct UnreachableTransport;
+
+#[async_trait::async_trait]
+impl HttpTransport for UnreachableTransport {
+    async fn send(&self, _: HttpRequest) -> ApolloClientResult<HttpResponse> {
+        Err(ApolloClientError::Transport("connection refused".into()))
+    }
+}
+
+/// Config service accepting the first `hangs` connections but never responding, as if it
+/// hangs, the remained connections are proxied to the upstream, return the url and the count
+/// of accepted connections.
+async fn hanging_service(hangs: usize, upstream: Option<Url>) -> (Url, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        let mut streams = Vec::new();
+        while let Ok((mut stream, _)) = listener.accept().await {
+            if accepted.fetch_add(1, Ordering::SeqCst) < hangs {
+                streams.push(stream);
+                continue;
+            }
+            let upstream = upstream.clone().unwrap().socket_addrs(|| None).unwrap();
+            tokio::spawn(async move {
+                let mut upstream = TcpStream::connect(&*upstream).await.unwrap();
+                let _ = copy_bidirectional(&mut stream, &mut upstream).await;
+            });
+        }
+    });
+
+    (url, connections)
+}
+
+/// Transport sending the requests to the mock, and recording the paths of them.
+#[derive(Clone)]
+struct RecordingTransport {
+    apollo: MockApollo,
+    paths: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingTransport {
+    fn new(apollo: &MockApollo) -> Self {
+        Self {
+            apollo: apollo.clone(),
+            paths: Default::default(),
+        }
+    }
+
+    fn count(&self, path: &str) -> usize {
+        let paths = self.paths.lock().unwrap();
+        paths.iter().filter(|p| p.starts_with(path)).count()
+    }
+
+    /// Wait until the count of the requests of the path reached.
+    async fn wait(&self, path: &str, count: usize) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while self.count(path) < count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpTransport for RecordingTransport {
+    async fn send(&self, request: HttpRequest) -> ApolloClientResult<HttpResponse> {
+        self.paths
+            .lock()
+            .unwrap()
+            .push(request.uri().path().to_string());
+        self.apollo.send(request).await
+    }
+}
+
+#[tokio::test]
+async fn test_fetch() {
+    let apollo = MockApollo::new();
+    let release_key = apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let client = apollo.conf_client_builder().build().unwrap();
+
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.namespace_name, "application");
+    assert_eq!(response.configurations["timeout"], "100");
+    assert_eq!(response.release_key, release_key);
+
+    let result = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            release_key: Some(release_key),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::NOT_MODIFIED
+    ));
+
+    let properties = client
+        .cached_fetch(CachedFetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application.properties".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(properties.get("timeout"), Some("100"));
+
+    let result = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "missing".to_string(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::NOT_FOUND
+    ));
+}
+
+#[tokio::test]
+async fn test_fail_over_timed_out_config_service() {
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let server = apollo.serve().await.unwrap();
+    let (hanging_url, connections) = hanging_service(usize::MAX, None).await;
+
+    // The hanging one is tried first in turn.
+    let client = ApolloConfClientBuilder::new_via_config_services([server.url(), hanging_url])
+        .unwrap()
+        .with_client_builder(|builder| builder.timeout(Duration::from_millis(200)))
+        .with_retry_policy(RetryPolicy::no_retry())
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        let response = client
+            .fetch(FetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.configurations["timeout"], "100");
+    }
+
+    // Skipped after timed out, as unhealthy.
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_fetch_via_meta_server() {
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let server = apollo.serve().await.unwrap();
+    let (hanging_url, connections) = hanging_service(usize::MAX, None).await;
+    // The hanging one is tried first in turn.
+    apollo.set_config_services([server.url(), hanging_url]);
+
+    let client = ApolloConfClientBuilder::new_via_meta_server(server.url())
+        .unwrap()
+        .with_client_builder(|builder| builder.timeout(Duration::from_millis(200)))
+        .with_retry_policy(RetryPolicy::no_retry())
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        let response = client
+            .fetch(FetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.configurations["timeout"], "100");
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_retry_timed_out_request() {
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let server = apollo.serve().await.unwrap();
+    let (url, connections) = hanging_service(1, Some(server.url())).await;
+
+    let client = ApolloConfClientBuilder::new_via_config_service(url)
+        .unwrap()
+        .with_client_builder(|builder| builder.timeout(Duration::from_millis(200)))
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.configurations["timeout"], "100");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_fetch_from_local_cache() {
+    let dir = std::env::temp_dir().join(format!("apollo-client-fallback-{}", std::process::id()));
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+
+    let client = apollo
+        .conf_client_builder()
+        .with_cache_dir(&dir)
+        .build()
+        .unwrap();
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application.properties".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(!response.from_cache);
+
+    let client = ApolloConfClientBuilder::new_via_config_service(MOCK_APOLLO_URL.parse().unwrap())
+        .unwrap()
+        .with_transport(UnreachableTransport)
+        .with_cache_dir(&dir)
+        .with_retry_policy(RetryPolicy::no_retry())
+        .build()
+        .unwrap();
+
+    // The name without suffix shares the cache of the canonical one.
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(response.from_cache);
+    assert_eq!(response.configurations["timeout"], "100");
+
+    let result = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "datasource.json".to_string(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(result, Err(ApolloClientError::Transport(_))));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_from_local_cache_on_timeout() {
+    let dir = std::env::temp_dir().join(format!("apollo-client-timeout-{}", std::process::id()));
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    apollo
+        .conf_client_builder()
+        .with_cache_dir(&dir)
+        .build()
+        .unwrap()
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let (hanging_url, _) = hanging_service(usize::MAX, None).await;
+    let client = ApolloConfClientBuilder::new_via_config_service(hanging_url)
+        .unwrap()
+        .with_client_builder(|builder| builder.timeout(Duration::from_millis(200)))
+        .with_cache_dir(&dir)
+        .with_retry_policy(RetryPolicy::no_retry())
+        .build()
+        .unwrap();
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(response.from_cache);
+    assert_eq!(response.configurations["timeout"], "100");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_watch() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let client = apollo.conf_client_builder().build().unwrap();
+
+    let stream = client.watch(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        namespace_names: vec!["application".into()],
+        ..Default::default()
+    });
+    futures_util::pin_mut!(stream);
+
+    let responses = stream.next().await.unwrap().unwrap();
+    assert_eq!(
+        responses["application"].as_ref().unwrap().configurations["timeout"],
+        "100"
+    );
+
+    // Released after some long polls timed out.
+    let publisher = apollo.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        publisher.set_item("SampleApp", "default", "application", "timeout", "200");
+        publisher.release("SampleApp", "default", "application");
+    });
+
+    let responses = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        responses["application"].as_ref().unwrap().configurations["timeout"],
+        "200"
+    );
+}
+
+#[tokio::test]
+async fn test_watch_stop() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_secs(1));
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let transport = RecordingTransport::new(&apollo);
+    let client = ApolloConfClientBuilder::new_via_config_service(MOCK_APOLLO_URL.parse().unwrap())
+        .unwrap()
+        .with_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let (handle, stream) = client.watch_with_handle(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        namespace_names: vec!["application".into()],
+        ..Default::default()
+    });
+    drop(client);
+
+    let task = tokio::spawn(async move {
+        futures_util::pin_mut!(stream);
+        let mut count = 0;
+        while stream.next().await.is_some() {
+            count += 1;
+        }
+        count
+    });
+
+    // The first long poll responds the initial notification id, the second one is pending.
+    transport.wait("/notifications/v2", 2).await;
+    assert!(!handle.is_stopped());
+    handle.stop();
+    assert!(handle.is_stopped());
+
+    // Not wait for the long poll timeout.
+    let count = tokio::time::timeout(Duration::from_millis(500), task)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(count, 1);
+
+    // No more long polls after stopped.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(transport.count("/notifications/v2"), 2);
+}
+
+#[tokio::test]
+async fn test_watch_subscribe() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish("SampleApp", "default", "foo1", [("foo1", "bar1")]);
+    apollo.publish("SampleApp", "default", "foo2", [("foo2", "bar2")]);
+    let transport = RecordingTransport::new(&apollo);
+    let client = ApolloConfClientBuilder::new_via_config_service(MOCK_APOLLO_URL.parse().unwrap())
+        .unwrap()
+        .with_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let (handle, stream) = client.watch_with_handle(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        ..Default::default()
+    });
+    futures_util::pin_mut!(stream);
+
+    // Nothing watched.
+    let responses = stream.next().await.unwrap().unwrap();
+    assert!(responses.is_empty());
+
+    assert!(handle.subscribe("foo1"));
+    assert!(!handle.subscribe("foo1.properties"));
+    let responses = stream.next().await.unwrap().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses["foo1"].as_ref().unwrap().configurations["foo1"],
+        "bar1"
+    );
+
+    // Only the new subscribed namespace is fetched.
+    assert!(handle.subscribe("foo2.properties"));
+    let responses = stream.next().await.unwrap().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses["foo2.properties"]
+            .as_ref()
+            .unwrap()
+            .configurations["foo2"],
+        "bar2"
+    );
+    assert_eq!(handle.namespace_names(), ["foo1", "foo2.properties"]);
+    assert_eq!(transport.count("/configs/"), 2);
+
+    assert!(handle.unsubscribe("foo1"));
+    assert!(handle.unsubscribe("foo2"));
+    assert!(!handle.unsubscribe("foo2"));
+    assert!(handle.namespace_names().is_empty());
+
+    // Idle without yielding or long polling.
+    let count = transport.count("/notifications/v2");
+    assert!(tokio::time::timeout(Duration::from_secs(1), stream.next())
+        .await
+        .is_err());
+    assert_eq!(transport.count("/notifications/v2"), count);
+    assert_eq!(transport.count("/configs/"), 2);
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_client() {
+    use apollo_client::conf::blocking::BlockingApolloConfClient;
+
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let client =
+        BlockingApolloConfClient::new(apollo.conf_client_builder().build().unwrap()).unwrap();
+
+    // Called from a thread without runtime.
+    std::thread::spawn(move || {
+        assert!(tokio::runtime::Handle::try_current().is_err());
+
+        let properties = client
+            .cached_fetch(CachedFetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(properties.get("timeout"), Some("100"));
+
+        let response = client
+            .fetch(FetchRequest {
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(response.configurations["timeout"], "100");
+
+        let mut watch = client.watch(WatchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_names: vec!["application".into()],
+            ..Default::default()
+        });
+        watch.next().unwrap().unwrap();
+
+        let publisher = apollo.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            publisher.publish("SampleApp", "default", "application", [("timeout", "200")]);
+        });
+        let responses = watch.next().unwrap().unwrap();
+        assert_eq!(
+            responses["application"].as_ref().unwrap().configurations["timeout"],
+            "200"
+        );
+
+        let handle = watch.handle().clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            handle.stop();
+        });
+        assert!(watch.next().is_none());
+    })
+    .join()
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_apollo_config() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let client = apollo.conf_client_builder().build().unwrap();
+
+    let config = ApolloConfig::new(
+        client,
+        WatchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_names: vec!["application".into(), "missing".into()],
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(config.get("application", "timeout").as_deref(), Some("100"));
+    assert_eq!(config.configurations("missing"), None);
+    assert_eq!(config.namespace_names(), ["application.properties"]);
+
+    // Refreshed in background after released.
+    let publisher = apollo.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        publisher.publish("SampleApp", "default", "application", [("timeout", "200")]);
+    });
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while config.get("application", "timeout").as_deref() != Some("200") {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_watch_changes() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish(
+        "SampleApp",
+        "default",
+        "application",
+        [("timeout", "100"), ("retries", "3")],
+    );
+    let client = apollo.conf_client_builder().build().unwrap();
+
+    let stream = client.watch_changes(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        namespace_names: vec!["application".into()],
+        ..Default::default()
+    });
+    futures_util::pin_mut!(stream);
+
+    let publisher = apollo.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        publisher.publish(
+            "SampleApp",
+            "default",
+            "application",
+            [("timeout", "200"), ("pool", "10")],
+        );
+    });
+
+    // The baseline is not yielded.
+    let event = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.namespace_name, "application");
+    let changes = event
+        .changes
+        .values()
+        .map(|change| {
+            (
+                change.key.as_str(),
+                change.change_type,
+                change.old_value.as_deref(),
+                change.new_value.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            ("pool", ChangeType::Added, None, Some("10")),
+            ("retries", ChangeType::Deleted, Some("3"), None),
+            ("timeout", ChangeType::Modified, Some("100"), Some("200")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_serve() {
+    let apollo = MockApollo::new();
+    apollo.set_long_poll_timeout(Duration::from_millis(100));
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let server = apollo.serve().await.unwrap();
+
+    let client = ApolloConfClientBuilder::new_via_config_service(server.url())
+        .unwrap()
+        .build()
+        .unwrap();
+    let response = client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.configurations["timeout"], "100");
+
+    let stream = client.watch(WatchRequest {
+        app_id: "SampleApp".to_string(),
+        namespace_names: vec!["application".into()],
+        ..Default::default()
+    });
+    futures_util::pin_mut!(stream);
+    stream.next().await.unwrap().unwrap();
+
+    let publisher = apollo.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        publisher.publish("SampleApp", "default", "application", [("timeout", "200")]);
+    });
+    let responses = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        responses["application"].as_ref().unwrap().configurations["timeout"],
+        "200"
+    );
+
+    let url = server.url();
+    drop(server);
+    let result = ApolloConfClientBuilder::new_via_config_service(url)
+        .unwrap()
+        .with_retry_policy(RetryPolicy::no_retry())
+        .build()
+        .unwrap()
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await;
+    assert!(result.unwrap_err().is_server_unavailable());
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_open() {
+    use apollo_client::open::{
+        meta::{OpenCreatedItem, OpenRelease, OpenUpdateItem},
+        requests::{
+            OpenCreateItemRequest, OpenDeleteItemRequest, OpenEnvClusterRequest,
+            OpenGetItemRequest, OpenGetNamespaceRequest, OpenLatestReleaseRequest,
+            OpenListItemsRequest, OpenNamespaceRequest, OpenPublishNamespaceRequest,
+            OpenUpdateItemRequest,
+        },
+    };
+
+    let apollo = MockApollo::new();
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let item = client
+        .create_item(OpenCreateItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            item: OpenCreatedItem {
+                key: "timeout".to_string(),
+                value: "100".to_string(),
+                data_change_created_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(item.value, "100");
+
+    client
+        .update_item(OpenUpdateItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            create_if_not_exists: true,
+            item: OpenUpdateItem {
+                key: "retries".to_string(),
+                value: "3".to_string(),
+                data_change_last_modified_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        apollo.configurations("SampleApp", "default", "application"),
+        None
+    );
+    let latest_release = client
+        .latest_release(OpenLatestReleaseRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(latest_release.is_none());
+
+    let response = client
+        .publish_namespace(OpenPublishNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            release: OpenRelease {
+                release_title: "release".to_string(),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.namespace_name, "application");
+
+    let configurations = apollo
+        .configurations("SampleApp", "default", "application")
+        .unwrap();
+    assert_eq!(configurations["timeout"], "100");
+    assert_eq!(configurations["retries"], "3");
+
+    let latest_release = client
+        .latest_release(OpenLatestReleaseRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest_release.name, "release");
+    assert_eq!(latest_release.configurations, configurations);
+
+    let env_clusters = client
+        .env_cluster(OpenEnvClusterRequest {
+            app_id: "SampleApp".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(env_clusters[0].clusters, ["default"]);
+
+    let namespaces = client
+        .namespace(OpenNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(namespaces.len(), 1);
+    assert_eq!(namespaces[0].namespace_name, "application");
+
+    let namespace = client
+        .get_namespace(OpenGetNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(namespace.format, "properties");
+    assert_eq!(namespace.items.len(), 2);
+
+    let page = client
+        .list_items(OpenListItemsRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            size: 1,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 2);
+    assert_eq!(page.content.len(), 1);
+
+    client
+        .delete_item(OpenDeleteItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            key: "retries".to_string(),
+            operator: "apollo".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let result = client
+        .get_item(OpenGetItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            key: "retries".to_string(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::NOT_FOUND
+    ));
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_rollback_release() {
+    use apollo_client::open::{
+        meta::OpenRelease,
+        requests::{OpenPublishNamespaceRequest, OpenRollbackReleaseRequest},
+    };
+
+    let apollo = MockApollo::new();
+    let open_client = apollo.open_client_builder().build().unwrap();
+    let conf_client = apollo.conf_client_builder().build().unwrap();
+
+    let publish = |timeout: &str| {
+        apollo.set_item("SampleApp", "default", "application", "timeout", timeout);
+        open_client.publish_namespace(OpenPublishNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            release: OpenRelease {
+                release_title: format!("timeout-{}", timeout),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    };
+    let first = publish("100").await.unwrap();
+    let second = publish("200").await.unwrap();
+    assert!(first.id.is_some());
+    assert_ne!(first.id, second.id);
+
+    open_client
+        .rollback_release(OpenRollbackReleaseRequest {
+            env: "DEV".to_string(),
+            release_id: second.id.unwrap(),
+            operator: "apollo".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let response = conf_client
+        .fetch(FetchRequest {
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.configurations["timeout"], "100");
+
+    // Only one active release remained.
+    let result = open_client
+        .rollback_release(OpenRollbackReleaseRequest {
+            env: "DEV".to_string(),
+            release_id: first.id.unwrap(),
+            operator: "apollo".to_string(),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::BAD_REQUEST
+    ));
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_release_history() {
+    use apollo_client::open::{
+        meta::OpenRelease,
+        requests::{
+            OpenGetReleaseRequest, OpenPublishNamespaceRequest, OpenReleaseHistoryRequest,
+            OpenRollbackReleaseRequest,
+        },
+    };
+
+    let apollo = MockApollo::new();
+    let client = apollo.open_client_builder().build().unwrap();
+
+    for (timeout, host) in [("100", "a"), ("200", "a"), ("200", "b")] {
+        apollo.set_item("SampleApp", "default", "application", "timeout", timeout);
+        apollo.set_item("SampleApp", "default", "application", "host", host);
+        client
+            .publish_namespace(OpenPublishNamespaceRequest {
+                env: "DEV".to_string(),
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                release: OpenRelease {
+                    release_title: format!("{}-{}", timeout, host),
+                    released_by: "apollo".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    let history = |page| {
+        client.release_history(OpenReleaseHistoryRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            page,
+            size: 2,
+            ..Default::default()
+        })
+    };
+    let first_page = history(0).await.unwrap();
+    assert_eq!(first_page.total, 3);
+    let names = first_page
+        .content
+        .iter()
+        .map(|release| release.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["200-b", "200-a"]);
+    let oldest = &history(1).await.unwrap().content[0];
+    assert_eq!(oldest.name, "100-a");
+
+    let diff = oldest.diff(&first_page.content[0]);
+    assert!(diff.added.is_empty() && diff.removed.is_empty());
+    assert_eq!(
+        diff.changed["timeout"],
+        ("100".to_string(), "200".to_string())
+    );
+    assert_eq!(diff.changed["host"], ("a".to_string(), "b".to_string()));
+
+    let latest_id = first_page.content[0].id.unwrap();
+    client
+        .rollback_release(OpenRollbackReleaseRequest {
+            env: "DEV".to_string(),
+            release_id: latest_id,
+            operator: "apollo".to_string(),
+        })
+        .await
+        .unwrap();
+    let release = client
+        .get_release(OpenGetReleaseRequest {
+            env: "DEV".to_string(),
+            release_id: latest_id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(release.name, "200-b");
+    assert!(release.is_abandoned);
+    assert!(!history(0).await.unwrap().content[1].is_abandoned);
+
+    for (env, release_id) in [("FAT", latest_id), ("DEV", 10000)] {
+        let result = client
+            .get_release(OpenGetReleaseRequest {
+                env: env.to_string(),
+                release_id,
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(ApolloClientError::ApolloResponse(e)) if e.status == StatusCode::NOT_FOUND
+        ));
+    }
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_release_diff() {
+    use apollo_client::open::{
+        meta::OpenRelease,
+        requests::{OpenLatestReleaseRequest, OpenPublishNamespaceRequest},
+    };
+
+    let apollo = MockApollo::new();
+    apollo.publish(
+        "SampleApp",
+        "default",
+        "application",
+        [("timeout", "100"), ("host", "a"), ("retry", "3")],
+    );
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let latest = client
+        .latest_release(OpenLatestReleaseRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    apollo.set_item("SampleApp", "default", "application", "timeout", "200");
+    apollo.set_item("SampleApp", "default", "application", "batch", "500");
+    let published = client
+        .publish_namespace(OpenPublishNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            release: OpenRelease {
+                release_title: "timeout".to_string(),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let diff = latest.diff(&published);
+    assert_eq!(
+        diff.added.into_iter().collect::<Vec<_>>(),
+        [("batch".to_string(), "500".to_string())]
+    );
+    assert_eq!(
+        diff.changed.into_iter().collect::<Vec<_>>(),
+        [(
+            "timeout".to_string(),
+            ("100".to_string(), "200".to_string())
+        )]
+    );
+    assert!(diff.removed.is_empty());
+    assert!(published.diff(&published).is_empty());
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_create_app_cluster_and_namespace() {
+    use apollo_client::{
+        meta::NamespaceKind,
+        open::{
+            meta::OpenCreatedApp,
+            requests::{
+                OpenCreateAppRequest, OpenCreateClusterRequest, OpenCreateNamespaceRequest,
+                OpenEnvClusterRequest, OpenLinkNamespaceRequest, OpenNamespaceRequest,
+            },
+        },
+    };
+
+    let apollo = MockApollo::new();
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let request = OpenCreateAppRequest {
+        app: OpenCreatedApp {
+            name: "New App".to_string(),
+            app_id: "NewApp".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    client.create_app(request.clone()).await.unwrap();
+    assert!(client.create_app(request).await.is_err());
+
+    let namespace = client
+        .create_namespace(OpenCreateNamespaceRequest {
+            app_id: "NewApp".to_string(),
+            namespace_name: "datasource".to_string(),
+            format: NamespaceKind::Json,
+            data_change_created_by: "apollo".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(namespace.format, "json");
+
+    let cluster = client
+        .create_cluster(OpenCreateClusterRequest {
+            env: "DEV".to_string(),
+            app_id: "NewApp".to_string(),
+            cluster_name: "canary".to_string(),
+            data_change_created_by: "apollo".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(cluster.name, "canary");
+
+    let request = OpenLinkNamespaceRequest {
+        env: "DEV".to_string(),
+        app_id: "NewApp".to_string(),
+        cluster_name: "canary".to_string(),
+        namespace_name: "TEST1.public".to_string(),
+        data_change_created_by: "apollo".to_string(),
+    };
+    let linked = client.link_namespace(request.clone()).await.unwrap();
+    assert_eq!(linked.namespace_name, "TEST1.public");
+    assert!(client.link_namespace(request).await.is_err());
+
+    let env_clusters = client
+        .env_cluster(OpenEnvClusterRequest {
+            app_id: "NewApp".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(env_clusters[0].clusters, ["canary", "default"]);
+
+    let namespaces = client
+        .namespace(OpenNamespaceRequest {
+            env: "DEV".to_string(),
+            app_id: "NewApp".to_string(),
+            cluster_name: "canary".to_string(),
+        })
+        .await
+        .unwrap();
+    let mut namespace_names = namespaces
+        .iter()
+        .map(|namespace| namespace.namespace_name.as_str())
+        .collect::<Vec<_>>();
+    namespace_names.sort_unstable();
+    assert_eq!(
+        namespace_names,
+        ["TEST1.public", "application", "datasource.json"]
+    );
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_sync() {
+    use apollo_client::open::{
+        meta::OpenRelease,
+        sync::{DesiredItem, SyncPlan, SyncTarget},
+    };
+    use std::collections::HashMap;
+
+    let apollo = MockApollo::new();
+    apollo.publish(
+        "SampleApp",
+        "default",
+        "application",
+        [("timeout", "100"), ("batch", "200"), ("retry", "3")],
+    );
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let target = SyncTarget {
+        env: "DEV".to_string(),
+        app_id: "SampleApp".to_string(),
+        namespace_name: "application".to_string(),
+        ..Default::default()
+    };
+    let desired = vec![
+        DesiredItem {
+            key: "timeout".to_string(),
+            value: "3000".to_string(),
+            comment: Some("timeout in milliseconds".to_string()),
+        },
+        DesiredItem {
+            key: "batch".to_string(),
+            value: "200".to_string(),
+            comment: None,
+        },
+        DesiredItem {
+            key: "port".to_string(),
+            value: "80".to_string(),
+            comment: None,
+        },
+    ];
+
+    let plan = SyncPlan::fetch(&client, target.clone(), desired.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        plan.to_string(),
+        "~ timeout = \"100\" -> \"3000\"\n+ port = \"80\"\n- retry\n"
+    );
+
+    let release = plan
+        .apply(
+            &client,
+            "apollo",
+            Some(OpenRelease {
+                release_title: "sync".to_string(),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        release.configurations,
+        HashMap::from([
+            ("timeout".to_string(), "3000".to_string()),
+            ("batch".to_string(), "200".to_string()),
+            ("port".to_string(), "80".to_string()),
+        ])
+    );
+
+    // Applied plan converges.
+    let plan = SyncPlan::fetch(&client, target, desired).await.unwrap();
+    assert!(!plan.has_changes());
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_compare_envs_and_promote() {
+    use apollo_client::open::{
+        meta::{OpenCreatedItem, OpenRelease},
+        promote::PromoteTarget,
+        requests::{
+            OpenCreateItemRequest, OpenEnvClusterRequest, OpenLatestReleaseRequest,
+            OpenPublishNamespaceRequest,
+        },
+    };
+    use std::collections::HashMap;
+
+    let apollo = MockApollo::new();
+    apollo.publish(
+        "SampleApp",
+        "default",
+        "application",
+        [("timeout", "3000"), ("batch", "500"), ("port", "80")],
+    );
+    let client = apollo.open_client_builder().build().unwrap();
+
+    // The methods of mock only serve `DEV`, fill `FAT` by the open apis.
+    for (key, value) in [("timeout", "100"), ("batch", "200"), ("host", "a")] {
+        client
+            .create_item(OpenCreateItemRequest {
+                env: "FAT".to_string(),
+                app_id: "SampleApp".to_string(),
+                namespace_name: "application".to_string(),
+                item: OpenCreatedItem {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    comment: None,
+                    data_change_created_by: "apollo".to_string(),
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+    client
+        .publish_namespace(OpenPublishNamespaceRequest {
+            env: "FAT".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            release: OpenRelease {
+                release_title: "init".to_string(),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let env_clusters = client
+        .env_cluster(OpenEnvClusterRequest {
+            app_id: "SampleApp".to_string(),
+        })
+        .await
+        .unwrap();
+    let envs = env_clusters
+        .iter()
+        .map(|env_cluster| env_cluster.env.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(envs, ["DEV", "FAT"]);
+
+    let target = PromoteTarget {
+        app_id: "SampleApp".to_string(),
+        namespace_name: "application".to_string(),
+        from_env: "DEV".to_string(),
+        to_env: "FAT".to_string(),
+        ..Default::default()
+    };
+    let diff = client.compare_envs(&target).await.unwrap();
+    assert_eq!(diff.added.keys().collect::<Vec<_>>(), ["port"]);
+    assert_eq!(
+        diff.changed.keys().collect::<Vec<_>>(),
+        ["batch", "timeout"]
+    );
+    assert_eq!(
+        diff.changed["timeout"],
+        ("100".to_string(), "3000".to_string())
+    );
+    assert_eq!(diff.removed.keys().collect::<Vec<_>>(), ["host"]);
+
+    let keys = ["timeout", "port", "host"].map(ToString::to_string);
+    let release = OpenRelease {
+        release_title: "promote".to_string(),
+        released_by: "apollo".to_string(),
+        ..Default::default()
+    };
+    let promotion = client
+        .promote(&target, &keys, release.clone())
+        .await
+        .unwrap();
+    assert!(promotion.plan.has_changes());
+    let promoted = promotion.release.unwrap();
+    assert_eq!(promoted.name, "promote");
+
+    // The `batch` is not promoted, and `DEV` is untouched.
+    let latest = client
+        .latest_release(OpenLatestReleaseRequest {
+            env: "FAT".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.id, promoted.id);
+    assert_eq!(
+        latest.configurations,
+        HashMap::from([
+            ("timeout".to_string(), "3000".to_string()),
+            ("batch".to_string(), "200".to_string()),
+            ("port".to_string(), "80".to_string()),
+        ])
+    );
+    assert_eq!(
+        apollo.configurations("SampleApp", "default", "application"),
+        Some(HashMap::from([
+            ("timeout".to_string(), "3000".to_string()),
+            ("batch".to_string(), "500".to_string()),
+            ("port".to_string(), "80".to_string()),
+        ]))
+    );
+
+    // Nothing to promote, no release.
+    let promotion = client.promote(&target, &keys, release).await.unwrap();
+    assert!(!promotion.plan.has_changes());
+    assert!(promotion.release.is_none());
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_export_and_import_namespaces() {
+    use apollo_client::open::{meta::OpenRelease, requests::OpenNamespaceRequest};
+    use std::{collections::HashMap, fs};
+
+    let apollo = MockApollo::new();
+    apollo.set_item("SampleApp", "default", "application", "timeout", "100");
+    apollo.set_item("SampleApp", "default", "application", "batch", "200");
+    apollo.set_item(
+        "SampleApp",
+        "default",
+        "datasource.json",
+        "content",
+        r#"{"url": "mysql://127.0.0.1"}"#,
+    );
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let dir = std::env::temp_dir().join(format!("apollo-client-snapshot-{}", std::process::id()));
+    let mut paths = client
+        .export_namespaces(
+            OpenNamespaceRequest {
+                env: "DEV".to_string(),
+                app_id: "SampleApp".to_string(),
+                ..Default::default()
+            },
+            &dir,
+        )
+        .await
+        .unwrap();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            dir.join("application.properties"),
+            dir.join("datasource.json")
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("datasource.json")).unwrap(),
+        r#"{"url": "mysql://127.0.0.1"}"#
+    );
+
+    apollo.set_item("OtherApp", "default", "application", "timeout", "300");
+    apollo.set_item("OtherApp", "default", "application", "retry", "3");
+
+    let plans = client
+        .import_namespaces(
+            OpenNamespaceRequest {
+                env: "DEV".to_string(),
+                app_id: "OtherApp".to_string(),
+                ..Default::default()
+            },
+            &dir,
+            "apollo",
+            Some(OpenRelease {
+                release_title: "import".to_string(),
+                released_by: "apollo".to_string(),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(plans.len(), 2);
+    fs::remove_dir_all(dir).unwrap();
+
+    // The items absent in the files are kept.
+    assert_eq!(
+        apollo.configurations("OtherApp", "default", "application"),
+        Some(HashMap::from([
+            ("timeout".to_string(), "100".to_string()),
+            ("batch".to_string(), "200".to_string()),
+            ("retry".to_string(), "3".to_string()),
+        ]))
+    );
+    assert_eq!(
+        apollo.configurations("OtherApp", "default", "datasource.json"),
+        Some(HashMap::from([(
+            "content".to_string(),
+            r#"{"url": "mysql://127.0.0.1"}"#.to_string()
+        )]))
+    );
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_export_and_import_yml_namespace() {
+    use apollo_client::open::{
+        meta::{OpenCreatedItem, OpenRelease},
+        requests::{
+            OpenCreateItemRequest, OpenGetItemRequest, OpenLatestReleaseRequest,
+            OpenNamespaceRequest,
+        },
+    };
+    use std::{collections::HashMap, fs};
+
+    let apollo = MockApollo::new();
+    apollo.set_item(
+        "SampleApp",
+        "default",
+        "datasource.yml",
+        "content",
+        "url: mysql://127.0.0.1",
+    );
+    let client = apollo.open_client_builder().build().unwrap();
+    client
+        .create_item(OpenCreateItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            item: OpenCreatedItem {
+                key: "timeout".to_string(),
+                value: "100".to_string(),
+                comment: Some("timeout in milliseconds".to_string()),
+                data_change_created_by: "apollo".to_string(),
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    apollo.release("SampleApp", "default", "application");
+    apollo.release("SampleApp", "default", "datasource.yml");
+
+    let request = OpenNamespaceRequest {
+        env: "DEV".to_string(),
+        app_id: "SampleApp".to_string(),
+        ..Default::default()
+    };
+    let release = OpenRelease {
+        release_title: "import".to_string(),
+        released_by: "apollo".to_string(),
+        ..Default::default()
+    };
+    let latest_release_id = || async {
+        client
+            .latest_release(OpenLatestReleaseRequest {
+                env: "DEV".to_string(),
+                app_id: "SampleApp".to_string(),
+                namespace_name: "datasource.yml".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .unwrap()
+            .id
+    };
+    let namespace_names = || async {
+        let mut namespace_names = client
+            .namespace(request.clone())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|namespace| namespace.namespace_name)
+            .collect::<Vec<_>>();
+        namespace_names.sort_unstable();
+        namespace_names
+    };
+
+    let dir = std::env::temp_dir().join(format!("apollo-client-yml-{}", std::process::id()));
+    let mut paths = client
+        .export_namespaces(request.clone(), &dir)
+        .await
+        .unwrap();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            dir.join("application.properties"),
+            dir.join("datasource.yml")
+        ]
+    );
+
+    // Import the exported files back, nothing changes and nothing is published.
+    let release_id = latest_release_id().await;
+    let plans = client
+        .import_namespaces(request.clone(), &dir, "apollo", Some(release.clone()))
+        .await
+        .unwrap();
+    assert!(plans.iter().all(|plan| !plan.has_changes()));
+    assert_eq!(latest_release_id().await, release_id);
+    assert_eq!(namespace_names().await, ["application", "datasource.yml"]);
+
+    // Import the changed file, the same namespace is updated and published, and the comments
+    // are kept.
+    fs::write(dir.join("datasource.yml"), "url: mysql://10.0.0.1").unwrap();
+    fs::write(dir.join("application.properties"), "timeout=200\n").unwrap();
+    let plans = client
+        .import_namespaces(request.clone(), &dir, "apollo", Some(release))
+        .await
+        .unwrap();
+    fs::remove_dir_all(dir).unwrap();
+    assert!(plans.iter().all(|plan| plan.has_changes()));
+    assert_ne!(latest_release_id().await, release_id);
+    assert_eq!(namespace_names().await, ["application", "datasource.yml"]);
+    assert_eq!(
+        apollo.configurations("SampleApp", "default", "datasource.yml"),
+        Some(HashMap::from([(
+            "content".to_string(),
+            "url: mysql://10.0.0.1".to_string()
+        )]))
+    );
+    let item = client
+        .get_item(OpenGetItemRequest {
+            env: "DEV".to_string(),
+            app_id: "SampleApp".to_string(),
+            namespace_name: "application".to_string(),
+            key: "timeout".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(item.value, "200");
+    assert_eq!(item.comment.as_deref(), Some("timeout in milliseconds"));
+}
+
+#[cfg(feature = "open")]
+#[tokio::test]
+async fn test_import_new_yml_namespace_twice() {
+    use apollo_client::open::{meta::OpenRelease, requests::OpenNamespaceRequest};
+    use std::{collections::HashMap, fs};
+
+    let apollo = MockApollo::new();
+    apollo.publish("SampleApp", "default", "application", [("timeout", "100")]);
+    let client = apollo.open_client_builder().build().unwrap();
+
+    let request = OpenNamespaceRequest {
+        env: "DEV".to_string(),
+        app_id: "SampleApp".to_string(),
+        ..Default::default()
+    };
+    let release = OpenRelease {
+        release_title: "import".to_string(),
+        released_by: "apollo".to_string(),
+        ..Default::default()
+    };
+    let dir = std::env::temp_dir().join(format!("apollo-client-new-yml-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("cache.yml"), "size: 100").unwrap();
+
+    // The namespace is created as `cache.yaml` by apollo, and matched by the second import.
+    let plans = client
+        .import_namespaces(request.clone(), &dir, "apollo", Some(release.clone()))
+        .await
+        .unwrap();
+    assert_eq!(plans[0].target.namespace_name, "cache.yaml");
+    assert!(plans[0].has_changes());
+    let plans = client
+        .import_namespaces(request.clone(), &dir, "apollo", Some(release.clone()))
+        .await
+        .unwrap();
+    fs::remove_dir_all(dir).unwrap();
+    assert_eq!(plans[0].target.namespace_name, "cache.yaml");
+    assert!(!plans[0].has_changes());
+
+    let mut namespace_names = client
+        .namespace(request)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|namespace| namespace.namespace_name)
+        .collect::<Vec<_>>();
+    namespace_names.sort_unstable();
+    assert_eq!(namespace_names, ["application", "cache.yaml"]);
+    assert_eq!(
+        apollo.configurations("SampleApp", "default", "cache.yaml"),
+        Some(HashMap::from([(
+            "content".to_string(),
+            "size: 100".to_string()
+        )]))
+    );
+}